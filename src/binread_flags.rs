@@ -1,5 +1,14 @@
 macro_rules! binread_flags {
     ($type: ty, $repr:ty) => {
+        binread_flags!(@impl $type, $repr, |raw| <$type>::from_bits(raw));
+    };
+
+    // unknown bits are kept instead of being rejected
+    ($type: ty, $repr:ty, retain_unknown_bits) => {
+        binread_flags!(@impl $type, $repr, |raw| Some(<$type>::from_bits_retain(raw)));
+    };
+
+    (@impl $type: ty, $repr:ty, $from_bits:expr) => {
         impl binrw::BinRead for $type {
             type Args<'a> = ();
 
@@ -14,7 +23,8 @@ macro_rules! binread_flags {
                     binrw::Endian::Little => reader.read_le()?,
                 };
 
-                match Self::from_bits(raw) {
+                let from_bits: fn($repr) -> Option<Self> = $from_bits;
+                match from_bits(raw) {
                     Some(res) => Ok(res),
                     None => Err(binrw::Error::AssertFail {
                         pos: reader.stream_position()?,
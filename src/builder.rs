@@ -0,0 +1,133 @@
+use crate::{ShellLink, ShowCommand, StringEncoding};
+
+/// Creates a [`ShellLink`] step by step. All values which are not specified
+/// keep the defaults of [`ShellLink::default`].
+///
+/// ```
+/// use lnk::{ShellLinkBuilder, ShowCommand};
+///
+/// let shortcut = ShellLinkBuilder::new()
+///     .relative_path(r".\notepad.exe")
+///     .working_dir(r"C:\Windows\System32")
+///     .show_command(ShowCommand::ShowMaximized)
+///     .build();
+/// assert_eq!(*shortcut.header().show_command(), ShowCommand::ShowMaximized);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ShellLinkBuilder {
+    encoding: Option<StringEncoding>,
+    name: Option<String>,
+    relative_path: Option<String>,
+    working_dir: Option<String>,
+    arguments: Option<String>,
+    icon_location: Option<String>,
+    icon_index: i32,
+    show_command: Option<ShowCommand>,
+}
+
+impl ShellLinkBuilder {
+    /// creates a new builder, which has no values specified
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sets the encoding of the strings of the link
+    pub fn encoding(mut self, encoding: StringEncoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// sets the name, which is shown as comment of the link
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// sets the relative path of the link target
+    pub fn relative_path(mut self, relative_path: impl Into<String>) -> Self {
+        self.relative_path = Some(relative_path.into());
+        self
+    }
+
+    /// sets the working directory
+    pub fn working_dir(mut self, working_dir: impl Into<String>) -> Self {
+        self.working_dir = Some(working_dir.into());
+        self
+    }
+
+    /// sets the command line arguments
+    pub fn arguments(mut self, arguments: impl Into<String>) -> Self {
+        self.arguments = Some(arguments.into());
+        self
+    }
+
+    /// sets the location of the icon
+    pub fn icon_location(mut self, icon_location: impl Into<String>) -> Self {
+        self.icon_location = Some(icon_location.into());
+        self
+    }
+
+    /// sets the index of the icon within the icon location. The default is
+    /// `0`
+    pub fn icon_index(mut self, icon_index: i32) -> Self {
+        self.icon_index = icon_index;
+        self
+    }
+
+    /// sets the window state of the application launched by the link
+    pub fn show_command(mut self, show_command: ShowCommand) -> Self {
+        self.show_command = Some(show_command);
+        self
+    }
+
+    /// creates the [`ShellLink`]
+    pub fn build(self) -> ShellLink {
+        let mut link = ShellLink::default();
+        if let Some(encoding) = &self.encoding {
+            link = link.with_encoding(encoding);
+        }
+        link.set_name(self.name);
+        link.set_relative_path(self.relative_path);
+        link.set_working_dir(self.working_dir);
+        link.set_arguments(self.arguments);
+        link.set_icon_location(self.icon_location);
+
+        let header = link.header_mut();
+        header.set_icon_index(self.icon_index);
+        if let Some(show_command) = self.show_command {
+            header.set_show_command(show_command);
+        }
+        link
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LinkFlags;
+
+    use super::*;
+
+    #[test]
+    fn test_icon_index_and_show_command() {
+        let link = ShellLinkBuilder::new()
+            .icon_index(2)
+            .show_command(ShowCommand::ShowMaximized)
+            .build();
+        assert_eq!(*link.header().icon_index(), 2);
+        assert_eq!(*link.header().show_command(), ShowCommand::ShowMaximized);
+        assert!(!link
+            .header()
+            .link_flags()
+            .contains(LinkFlags::HAS_ICON_LOCATION));
+
+        let link = ShellLinkBuilder::new()
+            .icon_location(r"%SystemRoot%\System32\shell32.dll")
+            .build();
+        assert_eq!(*link.header().icon_index(), 0);
+        assert_eq!(*link.header().show_command(), ShowCommand::ShowNormal);
+        assert!(link
+            .header()
+            .link_flags()
+            .contains(LinkFlags::HAS_ICON_LOCATION));
+    }
+}
@@ -0,0 +1,346 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use encoding_rs::{UTF_16LE, WINDOWS_1252};
+use getset::{CopyGetters, Getters};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{Guid, ItemID};
+
+/// The meaning of an [`ItemID`], as far as it could be decoded. The content of
+/// an ItemID is defined by the shell data source which created it, so only
+/// the most common item types are supported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum DecodedSegment {
+    /// a root folder, such as "My Computer", identified by its class id
+    RootFolder(Guid),
+
+    /// a volume, identified by its drive path (e.g. `C:\`)
+    Volume(String),
+
+    /// a file or a directory
+    FileEntry(FileEntry),
+
+    /// a network location, such as a server or a share, in UNC form
+    Network(String),
+
+    /// an URI
+    Url(String),
+
+    /// an item which could not be decoded. Contains the class type indicator
+    /// of the item, if there was one.
+    Unknown(Option<u8>),
+}
+
+/// A file or directory entry of an IDList
+#[derive(Clone, Debug, PartialEq, Eq, Getters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FileEntry {
+    /// returns `true` if this entry refers to a directory
+    #[getset(get_copy = "pub")]
+    is_directory: bool,
+
+    /// returns the size of the file, or zero for directories
+    #[getset(get_copy = "pub")]
+    file_size: u32,
+
+    /// returns the last modification time of the file, as a FAT date (in the
+    /// lower 16 bits) and time (in the upper 16 bits)
+    #[getset(get_copy = "pub")]
+    fat_modification_time: u32,
+
+    /// returns the file attributes
+    #[getset(get_copy = "pub")]
+    file_attributes: u16,
+
+    /// returns the primary name of the file, which is usually the 8.3 short
+    /// name
+    #[getset(get = "pub")]
+    short_name: String,
+
+    /// returns the long name of the file, if it is stored in the entry
+    #[getset(get = "pub")]
+    long_name: Option<String>,
+}
+
+impl FileEntry {
+    /// returns the long name of the file if there is one, or the short name
+    /// otherwise
+    pub fn name(&self) -> &str {
+        self.long_name.as_deref().unwrap_or(&self.short_name)
+    }
+
+    /// returns the last modification time of the file, which is decoded from
+    /// [`FileEntry::fat_modification_time`]. FAT times have a resolution of
+    /// two seconds and are usually stored in local time. Returns `None` if no
+    /// time is stored or it is not valid.
+    pub fn modification_time(&self) -> Option<NaiveDateTime> {
+        let date = self.fat_modification_time & 0xffff;
+        let time = self.fat_modification_time >> 16;
+        if date == 0 {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(1980 + (date >> 9) as i32, (date >> 5) & 0x0f, date & 0x1f)?
+            .and_hms_opt(time >> 11, (time >> 5) & 0x3f, (time & 0x1f) * 2)
+    }
+
+    /// returns the short name of the file
+    fn short_name_str(&self) -> &str {
+        &self.short_name
+    }
+}
+
+impl DecodedSegment {
+    /// the class id of the "My Computer" root folder
+    const MY_COMPUTER: uuid::Uuid = uuid::uuid!("20D04FE0-3AEA-1069-A2D8-08002B30309D");
+
+    /// appends this segment to `path`, using `name` to select the name of
+    /// file entries. Returns `false` if the segment cannot be part of a path.
+    fn append_to(&self, path: &mut Option<String>, name: fn(&FileEntry) -> &str) -> bool {
+        match self {
+            Self::RootFolder(guid) => *guid == Guid::from(Self::MY_COMPUTER),
+            Self::Volume(volume) | Self::Network(volume) | Self::Url(volume) => {
+                *path = Some(volume.clone());
+                true
+            }
+            Self::FileEntry(entry) => match path {
+                Some(path) => {
+                    if !path.ends_with('\\') {
+                        path.push('\\');
+                    }
+                    path.push_str(name(entry));
+                    true
+                }
+                None => false,
+            },
+            Self::Unknown(_) => false,
+        }
+    }
+}
+
+impl From<&ItemID> for DecodedSegment {
+    fn from(item: &ItemID) -> Self {
+        let data = item.data();
+        let class_type = match data.first() {
+            Some(class_type) => *class_type,
+            None => return Self::Unknown(None),
+        };
+
+        let segment = match class_type {
+            0x1f => data
+                .get(2..18)
+                .and_then(|bytes| uuid::Uuid::from_slice_le(bytes).ok())
+                .map(|uuid| Self::RootFolder(Guid::from(uuid))),
+            0x61 => decode_url(data),
+            t if t & 0x70 == 0x20 => read_ansi(data, 1).map(|(s, _)| Self::Volume(s)),
+            t if t & 0x70 == 0x30 => decode_file_entry(data).map(Self::FileEntry),
+            t if t & 0x70 == 0x40 => read_ansi(data, 3).map(|(s, _)| Self::Network(s)),
+            _ => None,
+        };
+        segment.unwrap_or(Self::Unknown(Some(class_type)))
+    }
+}
+
+/// assembles a path from `segments`, using the long names of file entries.
+/// Returns `None` if the segments do not start at a volume, a network location
+/// or an URI.
+pub(crate) fn segments_to_path<'a>(
+    segments: impl IntoIterator<Item = &'a DecodedSegment>,
+) -> Option<String> {
+    segments_to_path_with(segments, FileEntry::name)
+}
+
+/// assembles a path from `segments`, like [`segments_to_path`], but uses the
+/// short (8.3) names of file entries
+pub(crate) fn segments_to_short_path<'a>(
+    segments: impl IntoIterator<Item = &'a DecodedSegment>,
+) -> Option<String> {
+    segments_to_path_with(segments, FileEntry::short_name_str)
+}
+
+/// assembles an URL from `segments`, which must contain an URL, optionally
+/// followed by file entries. The names of the file entries are appended as
+/// path segments, and the URL is percent-encoded.
+pub(crate) fn segments_to_url(segments: &[DecodedSegment]) -> Option<String> {
+    let start = segments
+        .iter()
+        .rposition(|segment| matches!(segment, DecodedSegment::Url(_)))?;
+    let DecodedSegment::Url(url) = &segments[start] else {
+        return None;
+    };
+
+    let mut url = url.clone();
+    for segment in &segments[start + 1..] {
+        let DecodedSegment::FileEntry(entry) = segment else {
+            return None;
+        };
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        url.push_str(entry.name());
+    }
+    Some(percent_encode_url(&url))
+}
+
+/// checks whether `s` starts with an URL scheme, followed by `://`
+pub(crate) fn is_url(s: &str) -> bool {
+    s.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// percent-encodes all characters of `url` which may not appear in an URL
+/// (RFC 3986), using their UTF-8 representation. Reserved characters and
+/// existing percent-encoded octets are kept.
+pub(crate) fn percent_encode_url(url: &str) -> String {
+    const RESERVED: &[u8] = b"-._~:/?#[]@!$&'()*+,;=";
+
+    let bytes = url.as_bytes();
+    let mut encoded = String::with_capacity(url.len());
+    for (index, byte) in bytes.iter().enumerate() {
+        let is_escape = *byte == b'%'
+            && bytes
+                .get(index + 1..index + 3)
+                .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+        if byte.is_ascii_alphanumeric() || RESERVED.contains(byte) || is_escape {
+            encoded.push(char::from(*byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn segments_to_path_with<'a>(
+    segments: impl IntoIterator<Item = &'a DecodedSegment>,
+    name: fn(&FileEntry) -> &str,
+) -> Option<String> {
+    let mut path = None;
+    for segment in segments {
+        if !segment.append_to(&mut path, name) {
+            return None;
+        }
+    }
+    path
+}
+
+/// assembles a path in the shell namespace from `segments`. Root folders are
+/// written by their class id, segments which cannot be decoded are omitted.
+/// If nothing remains, the path of the desktop is returned.
+pub(crate) fn segments_to_shell_path<'a>(
+    segments: impl IntoIterator<Item = &'a DecodedSegment>,
+) -> String {
+    const DESKTOP: uuid::Uuid = uuid::uuid!("00021400-0000-0000-C000-000000000046");
+
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            DecodedSegment::RootFolder(guid) => path = format!("::{}", guid.to_canonical_string()),
+            DecodedSegment::Volume(volume)
+            | DecodedSegment::Network(volume)
+            | DecodedSegment::Url(volume) => path = volume.clone(),
+            DecodedSegment::FileEntry(entry) => {
+                if !path.is_empty() && !path.ends_with('\\') {
+                    path.push('\\');
+                }
+                path.push_str(entry.name());
+            }
+            DecodedSegment::Unknown(_) => (),
+        }
+    }
+    if path.is_empty() {
+        path = format!("::{}", Guid::from(DESKTOP).to_canonical_string());
+    }
+    path
+}
+
+/// reads a null terminated string in the default code page, starting at
+/// `offset`. Returns the string and the offset after the null terminator.
+fn read_ansi(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let bytes = data.get(offset..)?;
+    let len = bytes.iter().position(|b| *b == 0)?;
+    let (cow, _, _) = WINDOWS_1252.decode(&bytes[..len]);
+    Some((cow.to_string(), offset + len + 1))
+}
+
+/// reads a null terminated UTF-16LE string, starting at `offset`. Returns the
+/// string and the offset after the null terminator.
+fn read_unicode(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let bytes = data.get(offset..)?;
+    let len = bytes
+        .chunks_exact(2)
+        .position(|c| c == [0, 0])
+        .map(|p| p * 2)?;
+    let (cow, _, _) = UTF_16LE.decode(&bytes[..len]);
+    Some((cow.to_string(), offset + len + 2))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn decode_file_entry(data: &[u8]) -> Option<FileEntry> {
+    let class_type = data[0];
+    let (short_name, end) = if class_type & 0x04 != 0 {
+        read_unicode(data, 12)?
+    } else {
+        read_ansi(data, 12)?
+    };
+
+    // the extension block is aligned to 16 bits, counted from the start of
+    // the ItemID, which is two bytes before `data`
+    let extension_offset = end + end % 2;
+    let long_name = decode_file_entry_extension(data, extension_offset);
+
+    Some(FileEntry {
+        is_directory: class_type & 0x01 != 0,
+        file_size: read_u32(data, 2)?,
+        fat_modification_time: read_u32(data, 6)?,
+        file_attributes: read_u16(data, 10)?,
+        short_name,
+        long_name,
+    })
+}
+
+/// reads the long name from the `0xbeef0004` extension block of a file entry
+fn decode_file_entry_extension(data: &[u8], offset: usize) -> Option<String> {
+    let version = read_u16(data, offset + 2)?;
+    if read_u32(data, offset + 4)? != 0xbeef_0004 || version < 3 {
+        return None;
+    }
+
+    let mut name_offset = offset + if version >= 7 { 38 } else { 20 };
+    if version >= 8 {
+        name_offset += 4;
+    }
+    if version >= 9 {
+        name_offset += 4;
+    }
+    read_unicode(data, name_offset).map(|(s, _)| s)
+}
+
+fn decode_url(data: &[u8]) -> Option<DecodedSegment> {
+    let flags = *data.get(1)?;
+    if read_u16(data, 2)? != 0 {
+        return None;
+    }
+    let (url, _) = if flags & 0x80 != 0 {
+        read_unicode(data, 4)?
+    } else {
+        read_ansi(data, 4)?
+    };
+    Some(DecodedSegment::Url(url))
+}
@@ -0,0 +1,188 @@
+use getset::Getters;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// A technique which is used to hide a command in the command line
+/// arguments of a shell link
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum PayloadEncoding {
+    /// the `-EncodedCommand` parameter of PowerShell, or one of its
+    /// abbreviations like `-enc` or `-e`, followed by a base64 encoded
+    /// UTF-16LE string
+    PowerShellEncodedCommand,
+
+    /// a call of the .NET method `[Convert]::FromBase64String` with a base64
+    /// encoded string literal, which is usually used in PowerShell commands
+    FromBase64String,
+}
+
+/// A payload which has been found in the command line arguments of a shell
+/// link, see [`ShellLink::decode_encoded_arguments`](crate::ShellLink::decode_encoded_arguments)
+#[derive(Clone, Debug, PartialEq, Eq, Getters)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[getset(get = "pub")]
+pub struct DecodedPayload {
+    /// returns the technique which has been used to encode the payload
+    technique: PayloadEncoding,
+
+    /// returns the payload as it is found in the arguments
+    encoded: String,
+
+    /// returns the decoded payload
+    decoded: String,
+}
+
+/// the full name of the PowerShell parameter, which may be abbreviated
+const ENCODED_COMMAND: &str = "encodedcommand";
+
+/// the name of the .NET method, which is matched ignoring case
+const FROM_BASE64_STRING: &str = "frombase64string";
+
+/// searches `arguments` for encoded payloads and decodes them. Payloads which
+/// are not valid base64, or which do not decode to text, are skipped.
+pub(crate) fn decode_payloads(arguments: &str) -> Vec<DecodedPayload> {
+    let mut payloads = Vec::new();
+
+    let words: Vec<_> = arguments
+        .split_whitespace()
+        .map(|word| word.trim_matches(['"', '\'']))
+        .collect();
+    for pair in words.windows(2) {
+        if is_encoded_command_switch(pair[0]) {
+            if let Some(decoded) = decode_base64(pair[1]).and_then(|bytes| decode_utf16le(&bytes)) {
+                payloads.push(DecodedPayload {
+                    technique: PayloadEncoding::PowerShellEncodedCommand,
+                    encoded: pair[1].to_string(),
+                    decoded,
+                });
+            }
+        }
+    }
+
+    // the method name is ASCII, so the byte offsets of the lowercase string
+    // are valid in `arguments` as well
+    let lowercase = arguments.to_ascii_lowercase();
+    for (start, _) in lowercase.match_indices(FROM_BASE64_STRING) {
+        let rest = arguments[start + FROM_BASE64_STRING.len()..].trim_start();
+        let Some(rest) = rest.strip_prefix('(') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+            continue;
+        };
+        let literal = &rest[1..];
+        let Some(end) = literal.find(quote) else {
+            continue;
+        };
+        let encoded = &literal[..end];
+        if let Some(decoded) = decode_base64(encoded).and_then(|bytes| decode_text(&bytes)) {
+            payloads.push(DecodedPayload {
+                technique: PayloadEncoding::FromBase64String,
+                encoded: encoded.to_string(),
+                decoded,
+            });
+        }
+    }
+    payloads
+}
+
+/// checks whether `word` is the `-EncodedCommand` parameter of PowerShell.
+/// PowerShell accepts any unambiguous abbreviation as well as `-ec`, and
+/// both `/` and dashes (including en dash and em dash) as prefix.
+fn is_encoded_command_switch(word: &str) -> bool {
+    let Some(name) = word.strip_prefix(['-', '/', '\u{2013}', '\u{2014}', '\u{2015}']) else {
+        return false;
+    };
+    let name = name.to_ascii_lowercase();
+    !name.is_empty() && (ENCODED_COMMAND.starts_with(&name) || name == "ec")
+}
+
+/// decodes a string in the standard base64 alphabet, with or without
+/// padding. Returns `None` if `s` is not valid base64.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches('=');
+    if s.is_empty() || s.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+/// decodes UTF-16LE text, as used by `-EncodedCommand`
+fn decode_utf16le(bytes: &[u8]) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+/// decodes text of an unknown encoding, which is UTF-16LE if every second
+/// byte is zero, or UTF-8 otherwise
+fn decode_text(bytes: &[u8]) -> Option<String> {
+    let is_utf16le = bytes.len() >= 2 && bytes.iter().skip(1).step_by(2).all(|b| *b == 0);
+    if is_utf16le {
+        decode_utf16le(bytes)
+    } else {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("TWFu"), Some(b"Man".to_vec()));
+        assert_eq!(decode_base64("TWE="), Some(b"Ma".to_vec()));
+        assert_eq!(decode_base64("TQ"), Some(b"M".to_vec()));
+        assert_eq!(decode_base64("T"), None);
+        assert_eq!(decode_base64("TW!u"), None);
+    }
+
+    #[test]
+    fn test_from_base64_string() {
+        let payloads = decode_payloads(
+            "-c \"iex ([Text.Encoding]::UTF8.GetString([Convert]::FromBase64String('Y2FsYy5leGU=')))\"",
+        );
+        assert_eq!(
+            payloads,
+            vec![DecodedPayload {
+                technique: PayloadEncoding::FromBase64String,
+                encoded: "Y2FsYy5leGU=".to_string(),
+                decoded: "calc.exe".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_malformed_payload() {
+        assert!(decode_payloads("-enc not-base64!").is_empty());
+        assert!(decode_payloads("-ExecutionPolicy Bypass").is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::ErrorKind;
+use std::io::{Seek, SeekFrom};
 
 use thiserror::Error;
 
@@ -12,27 +12,60 @@ pub enum Error {
     #[error("The parsed file isn't a shell link.")]
     NotAShellLinkError,
 
-    #[error("Unexpected End-of-File while expecting a '{0}' instead")]
-    UnexpectedEof(&'static str),
+    #[error("Unexpected End-of-File while expecting a '{context}' instead")]
+    UnexpectedEof {
+        context: &'static str,
+        offset: Option<u64>,
+    },
 
-    #[error("Error while parsing {0}: {1}")]
-    BinReadError(&'static str, binrw::Error),
+    #[error("Error while parsing {context}: {source}")]
+    BinReadError {
+        context: &'static str,
+        source: binrw::Error,
+        offset: Option<u64>,
+    },
+
+    #[error("The ShellLinkHeader has a HeaderSize of {size} bytes instead of 76 bytes")]
+    InvalidHeaderSize { size: u32, offset: Option<u64> },
+
+    #[error("The LinkInfo structure has an implausible size of {size} bytes")]
+    InvalidLinkInfoSize { size: u32, offset: Option<u64> },
 
     #[error("Error while writing {0}: {1}")]
     BinWriteError(&'static str, binrw::Error),
+
+    #[error("The window style {style} is invalid; it must be 1, 3 or 7")]
+    InvalidWindowStyle { style: u32 },
+
+    #[error("The string {string:?} cannot be represented in {encoding}")]
+    UnrepresentableString {
+        string: String,
+        encoding: &'static str,
+    },
 }
 
 impl Error {
     /// creates an [`Error::BinReadError`] instance which wraps a [`binrw::Error`]
     /// together with some context information which describes where the error
-    /// has occurred.
-    pub fn while_parsing(context: &'static str, be: binrw::Error) -> Self {
-        if let binrw::Error::Io(ref why) = be {
-            if why.kind() == ErrorKind::UnexpectedEof {
-                return Self::UnexpectedEof(context);
+    /// has occurred. The offset of the error is taken from `be` if possible;
+    /// otherwise, the end of the stream is used for unexpected End-of-File
+    /// errors and the current position of `reader` for all other errors. The
+    /// position of `reader` is left unchanged.
+    pub fn while_parsing<R: Seek>(context: &'static str, be: binrw::Error, reader: &mut R) -> Self {
+        let offset = match error_position(&be) {
+            Some(pos) => Some(pos),
+            None if be.is_eof() => stream_len(reader).ok(),
+            None => reader.stream_position().ok(),
+        };
+        if be.is_eof() {
+            Self::UnexpectedEof { context, offset }
+        } else {
+            Self::BinReadError {
+                context,
+                source: be,
+                offset,
             }
         }
-        Self::BinReadError(context, be)
     }
 
     /// creates an [`Error::BinWriteError`] instance which wraps a [`binrw::Error`]
@@ -41,4 +74,56 @@ impl Error {
     pub fn while_writing(context: &'static str, be: binrw::Error) -> Self {
         Self::BinWriteError(context, be)
     }
+
+    /// returns the offset in the parsed stream where the error has occurred,
+    /// if it is known
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            Self::UnexpectedEof { offset, .. }
+            | Self::BinReadError { offset, .. }
+            | Self::InvalidHeaderSize { offset, .. }
+            | Self::InvalidLinkInfoSize { offset, .. } => *offset,
+            _ => None,
+        }
+    }
+}
+
+/// returns the position which is stored in a [`binrw::Error`], if there is one
+fn error_position(be: &binrw::Error) -> Option<u64> {
+    match be.root_cause() {
+        binrw::Error::BadMagic { pos, .. }
+        | binrw::Error::AssertFail { pos, .. }
+        | binrw::Error::Custom { pos, .. }
+        | binrw::Error::NoVariantMatch { pos }
+        | binrw::Error::EnumErrors { pos, .. } => Some(*pos),
+        _ => None,
+    }
+}
+
+/// returns the length of the stream, restoring the current position of
+/// `reader` afterwards
+fn stream_len<R: Seek>(reader: &mut R) -> std::io::Result<u64> {
+    let position = reader.stream_position()?;
+    let len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, ErrorKind};
+
+    use super::*;
+
+    #[test]
+    fn test_eof_keeps_position() {
+        let mut reader = Cursor::new([0u8; 16]);
+        reader.set_position(3);
+        let be = binrw::Error::Io(std::io::Error::from(ErrorKind::UnexpectedEof));
+
+        let error = Error::while_parsing("LinkInfo", be, &mut reader);
+        assert!(matches!(error, Error::UnexpectedEof { .. }));
+        assert_eq!(error.offset(), Some(16));
+        assert_eq!(reader.position(), 3);
+    }
 }
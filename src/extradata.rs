@@ -1,13 +1,16 @@
-use std::io::ErrorKind;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
 
-use binrw::{BinRead, BinReaderExt};
+use binrw::{BinRead, BinReaderExt, BinResult};
 use encoding_rs::Encoding;
+use getset::{Getters, MutGetters};
 #[allow(unused)]
 use log::{debug, error, info, trace, warn};
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+use crate::LinkFlags;
+
 use self::{
     console_data::ConsoleDataBlock, console_fe_data::ConsoleFEDataBlock,
     darwin_data::DarwinDataBlock, environment_variable_data::EnvironmentVariableDataBlock,
@@ -104,51 +107,540 @@ pub enum ExtraDataBlock {
     #[br(magic = 0xa0000008u32)]
     ShimProps(#[br(args(_block_size))] ShimDataBlock),
     #[br(magic = 0xa0000009u32)]
-    PropertyStoreProps(#[br(args(_block_size))] PropertyStoreDataBlock),
+    PropertyStoreProps(#[br(args(_block_size, _default_codepage))] PropertyStoreDataBlock),
     #[br(magic = 0xa000000au32)]
-    VistaAndAboveIdListProps(#[br(args(_block_size))] VistaAndAboveIdListDataBlock),
+    ShellItemIdentifiers(#[br(args(_block_size))] ShellItemIdentifiers),
     #[br(magic = 0xa000000bu32)]
     KnownFolderProps(#[br(args(_block_size))] KnownFolderDataBlock),
     #[br(magic = 0xa000000cu32)]
-    ShellItemIdentifiers(#[br(args(_block_size))] ShellItemIdentifiers),
+    VistaAndAboveIdListProps(#[br(args(_block_size))] VistaAndAboveIdListDataBlock),
+    /// a block with a signature which is not known to this crate, like a
+    /// vendor-specific block. Its data is kept as it is, so that it can be
+    /// written back unchanged
+    Unknown {
+        signature: u32,
+        #[br(count = _block_size.saturating_sub(8))]
+        data: Vec<u8>,
+    },
+}
+
+/// The type of an [`ExtraDataBlock`], without its contents
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ExtraDataKind {
+    Environment,
+    Console,
+    Tracker,
+    ConsoleFe,
+    SpecialFolder,
+    Darwin,
+    IconEnvironment,
+    Shim,
+    PropertyStore,
+    ShellItemIdentifiers,
+    KnownFolder,
+    VistaAndAboveIdList,
+    /// a block which is not known to this crate, identified by its signature
+    Unknown(u32),
+}
+
+impl ExtraDataBlock {
+    /// returns the type of this block
+    pub fn kind(&self) -> ExtraDataKind {
+        match self {
+            Self::EnvironmentProps(_) => ExtraDataKind::Environment,
+            Self::ConsoleProps(_) => ExtraDataKind::Console,
+            Self::TrackerProps(_) => ExtraDataKind::Tracker,
+            Self::ConsoleFeProps(_) => ExtraDataKind::ConsoleFe,
+            Self::SpecialFolderProps(_) => ExtraDataKind::SpecialFolder,
+            Self::DarwinProps(_) => ExtraDataKind::Darwin,
+            Self::IconEnvironmentProps(_) => ExtraDataKind::IconEnvironment,
+            Self::ShimProps(_) => ExtraDataKind::Shim,
+            Self::PropertyStoreProps(_) => ExtraDataKind::PropertyStore,
+            Self::ShellItemIdentifiers(_) => ExtraDataKind::ShellItemIdentifiers,
+            Self::KnownFolderProps(_) => ExtraDataKind::KnownFolder,
+            Self::VistaAndAboveIdListProps(_) => ExtraDataKind::VistaAndAboveIdList,
+            Self::Unknown { signature, .. } => ExtraDataKind::Unknown(*signature),
+        }
+    }
+
+    /// returns the signature which identifies the type of this block
+    pub fn signature(&self) -> u32 {
+        match self {
+            Self::EnvironmentProps(_) => 0xa0000001,
+            Self::ConsoleProps(_) => 0xa0000002,
+            Self::TrackerProps(_) => 0xa0000003,
+            Self::ConsoleFeProps(_) => 0xa0000004,
+            Self::SpecialFolderProps(_) => 0xa0000005,
+            Self::DarwinProps(_) => 0xa0000006,
+            Self::IconEnvironmentProps(_) => 0xa0000007,
+            Self::ShimProps(_) => 0xa0000008,
+            Self::PropertyStoreProps(_) => 0xa0000009,
+            Self::ShellItemIdentifiers(_) => 0xa000000a,
+            Self::KnownFolderProps(_) => 0xa000000b,
+            Self::VistaAndAboveIdListProps(_) => 0xa000000c,
+            Self::Unknown { signature, .. } => *signature,
+        }
+    }
+
+    /// returns the [`LinkFlags`] bit which announces the presence of this
+    /// block, if there is such a bit
+    pub fn link_flag(&self) -> Option<LinkFlags> {
+        match self {
+            Self::EnvironmentProps(_) => Some(LinkFlags::HAS_EXP_STRING),
+            Self::DarwinProps(_) => Some(LinkFlags::HAS_DARWIN_ID),
+            Self::IconEnvironmentProps(_) => Some(LinkFlags::HAS_EXP_ICON),
+            Self::ShimProps(_) => Some(LinkFlags::RUN_WITH_SHIM_LAYER),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug, Getters, MutGetters)]
 #[allow(missing_docs, unused)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ExtraData {
+    /// returns the ExtraData blocks, in the order in which they were stored
+    #[getset(get = "pub")]
     blocks: Vec<ExtraDataBlock>,
+
+    /// the bytes every block has been read from, or `None` if the block has
+    /// been created or modified since
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw_blocks: Vec<Option<Vec<u8>>>,
+
+    /// the code page the ANSI strings of the blocks have been read with, and
+    /// which is used to write them again
+    #[cfg_attr(feature = "serde", serde(skip))]
+    default_codepage: Option<&'static Encoding>,
+}
+
+impl ExtraData {
+    /// returns the ExtraData blocks for modification. Because any block might
+    /// be changed, the bytes they have been read from are discarded.
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn blocks_mut(&mut self) -> &mut Vec<ExtraDataBlock> {
+        self.raw_blocks.clear();
+        &mut self.blocks
+    }
+
+    /// returns the types of the blocks, in the order in which they were
+    /// stored. Every type is returned only once, even if there are several
+    /// blocks of that type.
+    pub fn block_kinds(&self) -> Vec<ExtraDataKind> {
+        let mut kinds = Vec::new();
+        for kind in self.blocks.iter().map(ExtraDataBlock::kind) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        kinds
+    }
+
+    /// returns the ConsoleDataBlock, which contains the display settings of
+    /// the console window of the link target, if there is one
+    pub fn console(&self) -> Option<&ConsoleDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::ConsoleProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the code page which is stored in the ConsoleFEDataBlock, and
+    /// which is used to display text in the console window of the link
+    /// target, if there is one
+    pub fn console_code_page(&self) -> Option<u32> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::ConsoleFeProps(block) => Some(*block.code_page()),
+            _ => None,
+        })
+    }
+
+    /// returns the DarwinDataBlock, which contains the Windows Installer
+    /// descriptor of an advertised application, if there is one
+    pub fn darwin(&self) -> Option<&DarwinDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::DarwinProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the EnvironmentVariableDataBlock, which contains the path to
+    /// the link target with environment variables (e.g.
+    /// `%windir%\notepad.exe`), if there is one
+    pub fn environment_variables(&self) -> Option<&EnvironmentVariableDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::EnvironmentProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the IconEnvironmentDataBlock, which contains the path to the
+    /// icon with environment variables, if there is one
+    pub fn icon_environment(&self) -> Option<&IconEnvironmentDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::IconEnvironmentProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the KnownFolderDataBlock, which specifies the known folder
+    /// the link target is located in, if there is one
+    pub fn known_folder(&self) -> Option<&KnownFolderDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::KnownFolderProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the PropertyStoreDataBlock, which contains additional
+    /// properties of the link, if there is one
+    pub fn property_store(&self) -> Option<&PropertyStoreDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::PropertyStoreProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the name of the compatibility layer which is applied by the
+    /// ShimDataBlock when the link target is activated (e.g. `WIN7RTM` or
+    /// `RunAsAdmin`), if there is one
+    pub fn shim_layer(&self) -> Option<&str> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::ShimProps(block) => Some(block.layer_name().as_str()),
+            _ => None,
+        })
+    }
+
+    /// returns the SpecialFolderDataBlock, which specifies the special folder
+    /// the link target is located in, if there is one
+    pub fn special_folder(&self) -> Option<&SpecialFolderDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::SpecialFolderProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the TrackerDataBlock, which is used by the Link Tracking
+    /// service to find a moved link target, if there is one
+    pub fn tracker(&self) -> Option<&TrackerDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::TrackerProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the VistaAndAboveIdListDataBlock, which contains an alternate
+    /// IDList of the link target, if there is one
+    pub fn vista_id_list(&self) -> Option<&VistaAndAboveIdListDataBlock> {
+        self.blocks.iter().find_map(|block| match block {
+            ExtraDataBlock::VistaAndAboveIdListProps(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// returns the bytes the block at `index` has been read from, if it has
+    /// not been modified since
+    #[cfg(feature = "binwrite")]
+    fn raw_block(&self, index: usize) -> Option<&[u8]> {
+        self.raw_blocks.get(index)?.as_deref()
+    }
+
+    /// adds `block` to this ExtraData. If `replace_existing` is set, an
+    /// existing block with the same signature is replaced; otherwise, the
+    /// block is appended.
+    pub(crate) fn add_block(&mut self, block: ExtraDataBlock, replace_existing: bool) {
+        if replace_existing {
+            if let Some((index, existing)) = self
+                .blocks
+                .iter_mut()
+                .enumerate()
+                .find(|(_, b)| b.signature() == block.signature())
+            {
+                *existing = block;
+                if let Some(raw) = self.raw_blocks.get_mut(index) {
+                    *raw = None;
+                }
+                return;
+            }
+        }
+        self.blocks.push(block);
+    }
+}
+
+/// the maximum number of padding bytes which are skipped when looking for the
+/// next ExtraData block
+const MAX_PADDING: u64 = 8;
+
+/// reads the BlockSize and BlockSignature at the current position of
+/// `reader` without consuming them, together with the number of remaining
+/// bytes. Returns `None` if there are less than 8 bytes left.
+fn peek_block_header<R: Read + Seek>(reader: &mut R) -> BinResult<Option<(u32, u32, u64)>> {
+    let position = reader.stream_position()?;
+    let remaining = reader.seek(SeekFrom::End(0))?.saturating_sub(position);
+    reader.seek(SeekFrom::Start(position))?;
+    let header: BinResult<(u32, u32)> = reader.read_le();
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(header
+        .ok()
+        .map(|(block_size, signature)| (block_size, signature, remaining)))
+}
+
+/// checks whether a block of any signature, which fits into the input,
+/// starts at the current position of `reader`, without consuming it
+fn peek_plausible_block<R: Read + Seek>(reader: &mut R) -> BinResult<bool> {
+    let header = peek_block_header(reader)?;
+    Ok(header.is_some_and(|(block_size, _, remaining)| {
+        block_size >= 8 && u64::from(block_size) <= remaining
+    }))
+}
+
+/// checks whether a block with a known signature, which fits into the
+/// input, starts at the current position of `reader`, without consuming it
+fn peek_known_block<R: Read + Seek>(reader: &mut R) -> BinResult<bool> {
+    let header = peek_block_header(reader)?;
+    Ok(header.is_some_and(|(block_size, signature, remaining)| {
+        block_size >= 8
+            && u64::from(block_size) <= remaining
+            && (0xa0000001..=0xa000000c).contains(&signature)
+    }))
+}
+
+/// checks whether the bytes at the current position of `reader` are a
+/// plausible start of the ExtraData, i.e. a TerminalBlock or the size of a
+/// block which fits into the input, without consuming them. The end of the
+/// input is accepted as well, because some creators omit the TerminalBlock.
+pub(crate) fn peek_extra_data_start<R: Read + Seek>(reader: &mut R) -> std::io::Result<bool> {
+    let position = reader.stream_position()?;
+    let remaining = reader.seek(SeekFrom::End(0))? - position;
+    reader.seek(SeekFrom::Start(position))?;
+    let mut block_size = [0u8; 4];
+    let result = reader.read_exact(&mut block_size);
+    reader.seek(SeekFrom::Start(position))?;
+    let block_size = u32::from_le_bytes(block_size);
+    Ok(remaining == 0
+        || result.is_ok()
+            && (block_size < 4 || (block_size >= 8 && u64::from(block_size) <= remaining)))
+}
+
+/// some creators align the ExtraData blocks, so that there are a few padding
+/// bytes in front of a block. If there is no plausible block at the current
+/// position, i.e. the BlockSize is less than 8 or exceeds the input, but
+/// there is a block with a known signature within the next [`MAX_PADDING`]
+/// bytes, the padding is skipped. Otherwise, the position is left unchanged.
+fn skip_padding<R: Read + Seek>(reader: &mut R) -> BinResult<()> {
+    if peek_plausible_block(reader)? {
+        return Ok(());
+    }
+    let start = reader.stream_position()?;
+    for padding in 1..=MAX_PADDING {
+        reader.seek(SeekFrom::Start(start + padding))?;
+        if peek_known_block(reader)? {
+            warn!("skipped {padding} padding bytes in front of ExtraData block at 0x{start:08x}");
+            return Ok(());
+        }
+    }
+    reader.seek(SeekFrom::Start(start))?;
+    Ok(())
+}
+
+/// reads the `block_size` bytes of the block starting at `block_start`, and
+/// restores the current position afterwards. Returns `None` if the block
+/// exceeds the input.
+fn read_raw_block<R: Read + Seek>(
+    reader: &mut R,
+    block_start: u64,
+    block_size: u32,
+) -> BinResult<Option<Vec<u8>>> {
+    let block_end = reader.stream_position()?;
+    reader.seek(SeekFrom::Start(block_start))?;
+    let mut raw = Vec::new();
+    reader
+        .by_ref()
+        .take(u64::from(block_size))
+        .read_to_end(&mut raw)?;
+    reader.seek(SeekFrom::Start(block_end))?;
+    Ok((raw.len() == block_size as usize).then_some(raw))
+}
+
+/// blocks which have not been modified since they were read are written
+/// byte-exact from the bytes they were read from. All other blocks are
+/// serialized from their fields, followed by the terminal block.
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for ExtraData {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + Seek>(
+        &self,
+        writer: &mut W,
+        _endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        use binrw::BinWriterExt;
+
+        for index in 0..self.blocks.len() {
+            self.write_block(writer, index)?;
+        }
+        writer.write_le(&0u32)
+    }
+}
+
+#[cfg(feature = "binwrite")]
+impl ExtraData {
+    /// writes the block at `index`, using the bytes it has been read from if
+    /// possible
+    fn write_block<W: std::io::Write + Seek>(&self, writer: &mut W, index: usize) -> BinResult<()> {
+        use binrw::BinWriterExt;
+
+        if let Some(raw) = self.raw_block(index) {
+            writer.write_all(raw)?;
+            return Ok(());
+        }
+
+        let default_codepage = self.default_codepage.unwrap_or(encoding_rs::WINDOWS_1252);
+        let block = &self.blocks[index];
+        let data = match block {
+            ExtraDataBlock::EnvironmentProps(block) => block.to_bytes(default_codepage),
+            ExtraDataBlock::ConsoleProps(block) => block.to_bytes(),
+            ExtraDataBlock::TrackerProps(block) => block.to_bytes(default_codepage),
+            ExtraDataBlock::ConsoleFeProps(block) => block.to_bytes(),
+            ExtraDataBlock::SpecialFolderProps(block) => block.to_bytes(),
+            ExtraDataBlock::DarwinProps(block) => block.to_bytes(default_codepage),
+            ExtraDataBlock::IconEnvironmentProps(block) => block.to_bytes(default_codepage),
+            ExtraDataBlock::ShimProps(block) => block.to_bytes(),
+            ExtraDataBlock::PropertyStoreProps(block) => block.property_store().to_vec(),
+            ExtraDataBlock::ShellItemIdentifiers(block) => block.to_bytes()?,
+            ExtraDataBlock::KnownFolderProps(block) => block.to_bytes(),
+            ExtraDataBlock::VistaAndAboveIdListProps(block) => block.to_bytes()?,
+            ExtraDataBlock::Unknown { data, .. } => data.clone(),
+        };
+        writer.write_le(&(8 + data.len() as u32))?;
+        writer.write_le(&block.signature())?;
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// returns the serialized block at `index`, or `None` if the block cannot
+    /// be written
+    pub(crate) fn block_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        self.write_block(&mut cursor, index)
+            .ok()
+            .map(|_| cursor.into_inner())
+    }
 }
 
 impl BinRead for ExtraData {
     type Args<'a> = (&'static Encoding,);
 
-    fn read_options<R: std::io::Read + std::io::Seek>(
+    fn read_options<R: Read + Seek>(
         reader: &mut R,
         _endian: binrw::Endian,
         args: Self::Args<'_>,
     ) -> binrw::BinResult<Self> {
         let mut blocks = Vec::new();
+        let mut raw_blocks = Vec::new();
         loop {
+            skip_padding(reader)?;
+            let block_start = reader.stream_position()?;
             let block_size: u32 = match reader.read_le() {
                 Ok(block_size) => block_size,
                 Err(binrw::Error::Io(why)) => {
                     if why.kind() == ErrorKind::UnexpectedEof {
                         break;
                     } else {
-                        return Err(binrw::Error::Io(why))
+                        return Err(binrw::Error::Io(why));
                     }
-                },
-                Err(why) => return Err(why)
+                }
+                Err(why) => return Err(why),
             };
-            
-            if block_size == 0 {
+
+            if block_size < 4 {
+                // the terminal block ends the list; some creators append more
+                // terminal blocks, so zero bytes up to the end of the input
+                // are consumed as padding. If anything else follows, the zero
+                // bytes are left for the trailer.
+                let terminal_end = reader.stream_position()?;
+                loop {
+                    match reader.read_le::<u8>() {
+                        Ok(0) => (),
+                        Ok(_) => {
+                            reader.seek(SeekFrom::Start(terminal_end))?;
+                            break;
+                        }
+                        Err(binrw::Error::Io(why)) if why.kind() == ErrorKind::UnexpectedEof => {
+                            break
+                        }
+                        Err(why) => return Err(why),
+                    }
+                }
                 break;
             } else {
                 let block: ExtraDataBlock = reader.read_le_args((block_size, args.0))?;
                 blocks.push(block);
+                raw_blocks.push(read_raw_block(reader, block_start, block_size)?);
             }
         }
-        Ok(Self { blocks })
+        Ok(Self {
+            blocks,
+            raw_blocks,
+            default_codepage: Some(args.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinReaderExt;
+    use encoding_rs::WINDOWS_1252;
+
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> (ExtraData, u64) {
+        let mut cursor = Cursor::new(bytes);
+        let extra_data: ExtraData = cursor.read_le_args((WINDOWS_1252,)).unwrap();
+        (extra_data, cursor.position())
+    }
+
+    #[test]
+    fn test_unknown_block_after_padding() {
+        // padding, a SpecialFolderDataBlock and an unknown block, which
+        // directly follows the misaligned SpecialFolderDataBlock
+        let mut bytes = vec![0; 4];
+        bytes.extend_from_slice(&0x10u32.to_le_bytes());
+        bytes.extend_from_slice(&0xa000_0005u32.to_le_bytes());
+        bytes.extend_from_slice(&0x24u32.to_le_bytes());
+        bytes.extend_from_slice(&0x14u32.to_le_bytes());
+        bytes.extend_from_slice(&0x10u32.to_le_bytes());
+        bytes.extend_from_slice(&0xa000_00ffu32.to_le_bytes());
+        // the data of the unknown block looks like a block header
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&0xa000_0005u32.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+
+        let (extra_data, position) = parse(&bytes);
+        assert_eq!(
+            extra_data.block_kinds(),
+            [
+                ExtraDataKind::SpecialFolder,
+                ExtraDataKind::Unknown(0xa000_00ff)
+            ]
+        );
+        assert_eq!(position, bytes.len() as u64);
+    }
+
+    #[test]
+    fn test_multiple_terminal_blocks() {
+        let (extra_data, position) = parse(&[0; 8]);
+        assert!(extra_data.blocks().is_empty());
+        assert_eq!(position, 8);
+
+        // zero bytes which are followed by other data belong to the trailer
+        let (extra_data, position) = parse(&[0, 0, 0, 0, 0, 0, 1, 0]);
+        assert!(extra_data.blocks().is_empty());
+        assert_eq!(position, 4);
     }
 }
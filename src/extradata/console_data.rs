@@ -3,6 +3,8 @@ use bitflags::bitflags;
 use encoding_rs::UTF_16LE;
 use getset::Getters;
 
+#[cfg(feature = "binwrite")]
+use crate::strings::fixed_size_bytes;
 use crate::{binread_flags::binread_flags, strings::FixedSizeString};
 
 #[cfg(feature = "serde")]
@@ -71,13 +73,39 @@ bitflags! {
 
 binread_flags!(FontFamilyFlags, u32);
 
+bitflags! {
+  /// The boolean settings of the console window, as returned by
+  /// [`ConsoleDataBlock::flags`]. The ConsoleDataBlock stores each of them
+  /// in a 32-bit field of its own; this type combines them in the order of
+  /// these fields.
+  ///
+  /// The bit values are defined by this crate only. No such value is stored
+  /// in a shell link, and they cannot be compared with any Win32 constant,
+  /// so a value obtained elsewhere must not be converted with `from_bits`.
+  #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+  #[cfg_attr(feature = "serde", derive(Serialize))]
+  pub struct ConsoleFlags: u32 {
+    /// The console window is opened in full-screen mode.
+    const FULL_SCREEN    = 0b0000_0001;
+    /// The console window is opened in QuickEdit mode, in which the mouse
+    /// can be used to cut, copy, and paste text.
+    const QUICK_EDIT     = 0b0000_0010;
+    /// The console window is opened in insert mode.
+    const INSERT_MODE    = 0b0000_0100;
+    /// The console window is positioned by the system.
+    const AUTO_POSITION  = 0b0000_1000;
+    /// Duplicates are removed from the history buffer.
+    const HISTORY_NO_DUP = 0b0001_0000;
+  }
+}
+
 /// The ConsoleDataBlock structure specifies the display settings to use
 /// when a link target specifies an application that is run in a console
 /// window.
 #[derive(Clone, Debug, Getters, BinRead)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size == 0x0000_00CC))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct ConsoleDataBlock {
     /// A 16-bit, unsigned integer that specifies the fill attributes that
@@ -169,3 +197,141 @@ pub struct ConsoleDataBlock {
     /// color for a character.
     color_table: [u32; 16],
 }
+
+impl ConsoleDataBlock {
+    /// returns the index into the [color table](Self::color_table) of the
+    /// foreground color of the console window, which is stored in the low
+    /// nibble of the FillAttributes
+    pub fn screen_foreground(&self) -> u8 {
+        foreground(&self.fill_attributes)
+    }
+
+    /// returns the index into the [color table](Self::color_table) of the
+    /// background color of the console window, which is stored in the high
+    /// nibble of the FillAttributes
+    pub fn screen_background(&self) -> u8 {
+        background(&self.fill_attributes)
+    }
+
+    /// returns the index into the [color table](Self::color_table) of the
+    /// foreground color of the console window popup, which is stored in the
+    /// low nibble of the PopupFillAttributes
+    pub fn popup_foreground(&self) -> u8 {
+        foreground(&self.popup_fill_attributes)
+    }
+
+    /// returns the index into the [color table](Self::color_table) of the
+    /// background color of the console window popup, which is stored in the
+    /// high nibble of the PopupFillAttributes
+    pub fn popup_background(&self) -> u8 {
+        background(&self.popup_fill_attributes)
+    }
+
+    /// returns the boolean settings of the console window, i.e. the fields
+    /// FullScreen, QuickEdit, InsertMode, AutoPosition and HistoryNoDup, as
+    /// [`ConsoleFlags`]
+    pub fn flags(&self) -> ConsoleFlags {
+        let mut flags = ConsoleFlags::empty();
+        flags.set(ConsoleFlags::FULL_SCREEN, self.full_screen);
+        flags.set(ConsoleFlags::QUICK_EDIT, self.quick_edit);
+        flags.set(ConsoleFlags::INSERT_MODE, self.insert_mode);
+        flags.set(ConsoleFlags::AUTO_POSITION, self.auto_position);
+        flags.set(ConsoleFlags::HISTORY_NO_DUP, self.history_no_dup);
+        flags
+    }
+
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(0xcc - 8);
+        bytes.extend_from_slice(&self.fill_attributes.bits().to_le_bytes());
+        bytes.extend_from_slice(&self.popup_fill_attributes.bits().to_le_bytes());
+        for value in [
+            self.screen_buffer_size_x,
+            self.screen_buffer_size_y,
+            self.window_size_x,
+            self.window_size_y,
+            self.window_origin_x,
+            self.window_origin_y,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [
+            self.unused1,
+            self.unused2,
+            self.font_size,
+            self.font_family.bits(),
+            self.font_weight,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend(fixed_size_bytes(&self.face_name, 64, UTF_16LE));
+        for value in [
+            self.cursor_size,
+            u32::from(self.full_screen),
+            u32::from(self.quick_edit),
+            u32::from(self.insert_mode),
+            u32::from(self.auto_position),
+            self.history_buffer_size,
+            self.number_of_history_buffers,
+            u32::from(self.history_no_dup),
+        ]
+        .into_iter()
+        .chain(self.color_table)
+        {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// returns the foreground color index (0-15) of `attributes`
+fn foreground(attributes: &FillAttributeFlags) -> u8 {
+    (attributes.bits() & 0x0f) as u8
+}
+
+/// returns the background color index (0-15) of `attributes`
+fn background(attributes: &FillAttributeFlags) -> u8 {
+    ((attributes.bits() >> 4) & 0x0f) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinReaderExt;
+
+    use super::*;
+
+    #[test]
+    fn test_fill_attribute_colors() {
+        let mut bytes = vec![0u8; 0xcc - 8];
+        bytes[0..2].copy_from_slice(&0x0007u16.to_le_bytes());
+        bytes[2..4].copy_from_slice(&0x00f5u16.to_le_bytes());
+        let block: ConsoleDataBlock = Cursor::new(bytes).read_le_args((0xcc,)).unwrap();
+
+        assert_eq!(block.screen_foreground(), 7);
+        assert_eq!(block.screen_background(), 0);
+        assert_eq!(block.popup_foreground(), 5);
+        assert_eq!(block.popup_background(), 15);
+    }
+
+    #[test]
+    fn test_flags() {
+        // only the FullScreen and HistoryNoDup fields are set
+        let mut bytes = vec![0u8; 0xcc - 8];
+        bytes[104..108].copy_from_slice(&1u32.to_le_bytes());
+        bytes[128..132].copy_from_slice(&1u32.to_le_bytes());
+        let block: ConsoleDataBlock = Cursor::new(bytes).read_le_args((0xcc,)).unwrap();
+
+        assert!(block.full_screen());
+        assert!(!block.quick_edit());
+        assert!(!block.insert_mode());
+        assert!(!block.auto_position());
+        assert!(block.history_no_dup());
+        assert_eq!(
+            block.flags(),
+            ConsoleFlags::FULL_SCREEN | ConsoleFlags::HISTORY_NO_DUP
+        );
+    }
+}
@@ -10,7 +10,7 @@ use serde::Serialize;
 #[derive(Clone, Copy, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size == 0x0000_0000C))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct ConsoleFEDataBlock {
     /// A 32-bit, unsigned integer that specifies a code page language
@@ -18,3 +18,11 @@ pub struct ConsoleFEDataBlock {
     /// meaning of language code identifiers, see [MS-LCID].
     code_page: u32,
 }
+
+impl ConsoleFEDataBlock {
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        self.code_page.to_le_bytes().to_vec()
+    }
+}
@@ -5,7 +5,9 @@ use getset::Getters;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use crate::strings::FixedSizeString;
+#[cfg(feature = "binwrite")]
+use crate::strings::fixed_size_bytes;
+use crate::{strings::FixedSizeString, Guid};
 
 /// The DarwinDataBlock structure specifies an application identifier
 /// that can be used instead of a link target IDList to install an
@@ -13,8 +15,7 @@ use crate::strings::FixedSizeString;
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32, default_codepage: &'static Encoding), pre_assert(block_size == 0x0000_00314))]
-#[get(get = "pub")]
-#[allow(unused)]
+#[getset(get = "pub")]
 pub struct DarwinDataBlock {
     /// A NULL–terminated string, defined by the system default code
     /// page, which specifies an application identifier. This field
@@ -27,3 +28,118 @@ pub struct DarwinDataBlock {
     #[br(args(520, UTF_16LE), map=|s: FixedSizeString| if s.is_empty() {None} else {Some(s.to_string())})]
     darwin_data_unicode: Option<String>,
 }
+
+impl DarwinDataBlock {
+    /// serializes this block, without its size and signature. The ANSI
+    /// string is encoded using `default_codepage`.
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self, default_codepage: &'static Encoding) -> Vec<u8> {
+        let mut bytes = fixed_size_bytes(&self.darwin_data_ansi, 260, default_codepage);
+        bytes.extend(fixed_size_bytes(
+            self.darwin_data_unicode.as_deref().unwrap_or_default(),
+            520,
+            UTF_16LE,
+        ));
+        bytes
+    }
+
+    /// returns the Windows Installer descriptor of the advertised
+    /// application, exactly as it is stored. The Unicode string is preferred
+    /// if it exists.
+    ///
+    /// The descriptor consists of the packed product code, the feature name
+    /// and the packed component code.
+    pub fn descriptor(&self) -> &str {
+        self.darwin_data_unicode
+            .as_deref()
+            .unwrap_or(&self.darwin_data_ansi)
+    }
+
+    /// returns the product code which is contained in the descriptor
+    pub fn product_code(&self) -> Option<Guid> {
+        self.descriptor()
+            .get(..PACKED_GUID_LENGTH)
+            .and_then(unpack_guid)
+    }
+
+    /// returns the name of the feature which is contained in the descriptor
+    pub fn feature_name(&self) -> Option<&str> {
+        let (feature, _) = self
+            .descriptor()
+            .get(PACKED_GUID_LENGTH..)?
+            .split_once(['>', '<'])?;
+        Some(feature)
+    }
+
+    /// returns the component code which is contained in the descriptor
+    pub fn component_code(&self) -> Option<Guid> {
+        let (_, component) = self
+            .descriptor()
+            .get(PACKED_GUID_LENGTH..)?
+            .split_once(['>', '<'])?;
+        component.get(..PACKED_GUID_LENGTH).and_then(unpack_guid)
+    }
+}
+
+/// the number of characters of a packed GUID
+const PACKED_GUID_LENGTH: usize = 20;
+
+/// the characters which are used to pack GUIDs, in the order of their value
+const PACKED_GUID_ALPHABET: &[u8] =
+    b"!$%&'()*+,-.0123456789=?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[]^_`abcdefghijklmnopqrstuvwxyz{}~";
+
+/// decodes a GUID which has been packed by Windows Installer. Every five
+/// characters encode four bytes of the GUID in base 85, least significant
+/// digit first.
+fn unpack_guid(packed: &str) -> Option<Guid> {
+    let mut bytes = [0u8; 16];
+    for (chunk, target) in packed
+        .as_bytes()
+        .chunks_exact(5)
+        .zip(bytes.chunks_exact_mut(4))
+    {
+        let mut value = 0u32;
+        for c in chunk.iter().rev() {
+            let digit = PACKED_GUID_ALPHABET.iter().position(|a| a == c)?;
+            value = value.checked_mul(85)?.checked_add(digit as u32)?;
+        }
+        target.copy_from_slice(&value.to_le_bytes());
+    }
+    Some(Guid::from(uuid::Uuid::from_bytes_le(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinReaderExt;
+    use encoding_rs::WINDOWS_1252;
+
+    use super::*;
+
+    const DESCRIPTOR: &str = "w_1^VX!!!!!!!!!MKKSkEXCELFiles>tW{~$4Q]c@II=l2xaTO5";
+
+    #[test]
+    fn test_descriptor() {
+        let mut bytes = vec![0u8; 260 + 520];
+        bytes[..DESCRIPTOR.len()].copy_from_slice(DESCRIPTOR.as_bytes());
+        for (i, c) in DESCRIPTOR.encode_utf16().enumerate() {
+            bytes[260 + 2 * i..262 + 2 * i].copy_from_slice(&c.to_le_bytes());
+        }
+        let block: DarwinDataBlock = Cursor::new(bytes)
+            .read_le_args((0x314, WINDOWS_1252))
+            .unwrap();
+
+        assert_eq!(block.descriptor(), DESCRIPTOR);
+        assert_eq!(block.descriptor().len(), 2 * PACKED_GUID_LENGTH + 11);
+        assert_eq!(
+            block.product_code(),
+            Some(Guid::from(uuid::uuid!(
+                "91120000-0030-0000-0000-0000000FF1CE"
+            )))
+        );
+        assert_eq!(block.feature_name(), Some("EXCELFiles"));
+        assert!(block.component_code().is_some());
+        assert_eq!(unpack_guid("w_1^VX!!!!!!!!!MKKS\u{7f}"), None);
+    }
+}
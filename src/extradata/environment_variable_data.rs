@@ -5,6 +5,8 @@ use getset::Getters;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+#[cfg(feature = "binwrite")]
+use crate::strings::fixed_size_bytes;
 use crate::strings::FixedSizeString;
 
 /// The EnvironmentVariableDataBlock structure specifies a path to
@@ -13,7 +15,7 @@ use crate::strings::FixedSizeString;
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32, default_codepage: &'static Encoding), pre_assert(block_size == 0x0000_0314))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct EnvironmentVariableDataBlock {
     /// A NULL-terminated string, defined by the system default code
@@ -25,3 +27,29 @@ pub struct EnvironmentVariableDataBlock {
     #[br(args(520, UTF_16LE), map=|s: FixedSizeString| if s.is_empty() {None} else {Some(s.to_string())})]
     target_unicode: Option<String>,
 }
+
+impl EnvironmentVariableDataBlock {
+    /// returns the path to environment variable information. The Unicode
+    /// path is preferred if it is present. Both paths have a fixed size and
+    /// encoding, so this does not depend on the
+    /// [`LinkFlags::IS_UNICODE`](crate::LinkFlags::IS_UNICODE) flag, which
+    /// applies to the [`StringData`](crate::StringData) only.
+    pub fn target(&self) -> &str {
+        self.target_unicode
+            .as_deref()
+            .unwrap_or(self.target_ansi.as_str())
+    }
+
+    /// serializes this block, without its size and signature. The ANSI
+    /// string is encoded using `default_codepage`.
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self, default_codepage: &'static Encoding) -> Vec<u8> {
+        let mut bytes = fixed_size_bytes(&self.target_ansi, 260, default_codepage);
+        bytes.extend(fixed_size_bytes(
+            self.target_unicode.as_deref().unwrap_or_default(),
+            520,
+            UTF_16LE,
+        ));
+        bytes
+    }
+}
@@ -1,10 +1,11 @@
 use binrw::BinRead;
 use getset::Getters;
+use uuid::{uuid, Uuid};
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use crate::Guid;
+use crate::{Guid, IdList};
 
 /// The KnownFolderDataBlock structure specifies the location of a
 /// known folder. This data can be used when a link target is a
@@ -13,7 +14,7 @@ use crate::Guid;
 #[derive(Clone, Copy, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size == 0x0000_0001C))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct KnownFolderDataBlock {
     /// A value in GUID packet representation ([MS-DTYP] section
@@ -25,3 +26,63 @@ pub struct KnownFolderDataBlock {
     /// link target IDList.
     offset: u32,
 }
+
+/// the most common known folders, with names derived from their `FOLDERID_*`
+/// constants
+#[rustfmt::skip]
+const KNOWN_FOLDERS: [(Uuid, &str); 26] = [
+    (uuid!("B4BFCC3A-DB2C-424C-B029-7FE99A87C641"), "Desktop"),
+    (uuid!("FDD39AD0-238F-46AF-ADB4-6C85480369C7"), "Documents"),
+    (uuid!("374DE290-123F-4565-9164-39C4925E467B"), "Downloads"),
+    (uuid!("4BD8D571-6D19-48D3-BE97-422220080E43"), "Music"),
+    (uuid!("33E28130-4E1E-4676-835A-98395C3BC3BB"), "Pictures"),
+    (uuid!("18989B1D-99B5-455B-841C-AB7C74E4DDFC"), "Videos"),
+    (uuid!("5E6C858F-0E22-4760-9AFE-EA3317B67173"), "Profile"),
+    (uuid!("1777F761-68AD-4D8A-87BD-30B759FA33DD"), "Favorites"),
+    (uuid!("AE50C081-EBD2-438A-8655-8A092E34987A"), "Recent"),
+    (uuid!("3EB685DB-65F9-4CF6-A03A-E3EF65729F3D"), "Roaming AppData"),
+    (uuid!("F1B32785-6FBA-4FCF-9D55-7B8E7F157091"), "Local AppData"),
+    (uuid!("625B53C3-AB48-4EC1-BA1F-A1EF4146FC19"), "Start Menu"),
+    (uuid!("A77F5D77-2E2B-44C3-A6A2-ABA601054A51"), "Programs"),
+    (uuid!("B97D20BB-F46A-4C97-BA10-5E3608430854"), "Startup"),
+    (uuid!("A4115719-D62E-491D-AA7C-E74B8BE3B067"), "Common Start Menu"),
+    (uuid!("0139D44E-6AFE-49F2-8690-3DAFCAE6FFB8"), "Common Programs"),
+    (uuid!("DFDF76A2-C82A-4D63-906A-5644AC457385"), "Public"),
+    (uuid!("C4AA340D-F20F-4863-AFEF-F87EF2E6BA25"), "Public Desktop"),
+    (uuid!("62AB5D82-FDC1-4DC3-A9DD-070D1D495D97"), "Program Data"),
+    (uuid!("905E63B6-C1BF-494E-B29C-65B732D3D21A"), "Program Files"),
+    (uuid!("7C5A40EF-A0FB-4BFC-874A-C0F2E0B9FA8E"), "Program Files (x86)"),
+    (uuid!("F7F1ED05-9F6D-47A2-AAAE-29D317C6F066"), "Program Files Common"),
+    (uuid!("F38BF404-1D43-42F2-9305-67DE0B28FC23"), "Windows"),
+    (uuid!("1AC14E77-02E7-4E5D-B744-2EB1AE5198B7"), "System"),
+    (uuid!("D65231B0-B2F1-4857-A4CE-A8E7C6EA7D27"), "System (x86)"),
+    (uuid!("0AC0837C-BBF8-452A-850D-79D08E667CA7"), "Computer"),
+];
+
+impl KnownFolderDataBlock {
+    /// returns a human-readable name of the known folder, like `Desktop` or
+    /// `Program Files`. Only the most common known folders are supported.
+    pub fn name(&self) -> Option<&'static str> {
+        KNOWN_FOLDERS
+            .iter()
+            .find(|(id, _)| Guid::from(*id) == self.known_folder_id)
+            .map(|(_, name)| *name)
+    }
+
+    /// returns the index of the ItemID in `id_list` which [`Self::offset`]
+    /// points to, i.e. the first item which is relative to the known folder.
+    /// If the offset points to the end of the list, the length of the list is
+    /// returned. Returns `None` if the offset does not point to the start of
+    /// an ItemID of the list.
+    pub fn item_index(&self, id_list: &IdList) -> Option<usize> {
+        id_list.item_index(self.offset)
+    }
+
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = Uuid::from(self.known_folder_id).to_bytes_le().to_vec();
+        bytes.extend_from_slice(&self.offset.to_le_bytes());
+        bytes
+    }
+}
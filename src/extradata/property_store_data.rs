@@ -1,31 +1,808 @@
-use std::{fmt, mem::size_of};
+use std::{
+    fmt,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    mem::size_of,
+};
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use binrw::BinRead;
+use binrw::{BinRead, BinReaderExt, BinResult, BinWriterExt};
+use encoding_rs::{Encoding, UTF_16LE, WINDOWS_1252};
 use getset::Getters;
+#[allow(unused)]
+use log::{debug, error, info, trace, warn};
+
+use crate::{FileTime, Guid};
 
 /// A PropertyStoreDataBlock structure specifies a set of properties
 /// that can be used by applications to store extra data in the
 /// shell link.
-/// TODO: implement <https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-propstore/1eb58eb3-e7d8-4a09-ac0e-8bcb14b6fa0e>
 #[derive(Clone, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
-#[br(import(block_size: u32), pre_assert(block_size >= 0x0000_000C))]
-#[get(get = "pub")]
-#[allow(unused)]
+#[br(import(block_size: u32, default_codepage: &'static Encoding), pre_assert(block_size >= 0x0000_000C))]
+#[getset(get = "pub")]
 pub struct PropertyStoreDataBlock {
     /// A serialized property storage structure ([MS-PROPSTORE] section 2.2).
     #[br(count=block_size - u32::try_from(2*size_of::<u32>()).unwrap())]
+    #[cfg_attr(feature = "serde", serde(skip))]
     property_store: Vec<u8>,
+
+    /// returns the property sets which have been parsed from the serialized
+    /// property storage structure
+    #[br(calc = parse_property_stores(&property_store, default_codepage))]
+    property_stores: Vec<PropertyStore>,
+}
+
+impl PropertyStoreDataBlock {
+    /// returns the value of the property identified by `format_id` and `key`
+    pub fn get(&self, format_id: &Guid, key: &PropertyKey) -> Option<&PropVariant> {
+        self.property_stores
+            .iter()
+            .filter(|store| store.format_id() == format_id)
+            .flat_map(|store| store.properties().iter())
+            .find_map(|(k, v)| (k == key).then_some(v))
+    }
+
+    /// returns the value of `System.AppUserModel.ID`, which is used by
+    /// Windows to group the windows of an application on the taskbar
+    pub fn app_user_model_id(&self) -> Option<String> {
+        self.get(
+            &Guid::from(APP_USER_MODEL),
+            &PropertyKey::Integer(APP_USER_MODEL_ID),
+        )
+        .and_then(PropVariant::as_str)
+        .map(str::to_string)
+    }
 }
 
 impl fmt::Debug for PropertyStoreDataBlock {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "PropertyStoreDataBlock {{ property_store: (serialized property storage structure) }}"
-        )
+        f.debug_struct("PropertyStoreDataBlock")
+            .field("property_stores", &self.property_stores)
+            .finish()
+    }
+}
+
+/// A serialized property storage structure ([MS-PROPSTORE] section 2.2),
+/// which contains the properties of one property set
+#[derive(Clone, Debug, Getters)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[getset(get = "pub")]
+pub struct PropertyStore {
+    /// returns the identifier of the property set
+    format_id: Guid,
+
+    /// returns the properties of this set, together with their keys
+    properties: Vec<(PropertyKey, PropVariant)>,
+
+    /// returns the serialized value of each property, i.e. the bytes of the
+    /// typed property value including its vartype and padding
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw_values: Vec<(PropertyId, Vec<u8>)>,
+}
+
+impl PropertyStore {
+    /// appends a property, together with its serialized value
+    fn push(&mut self, key: PropertyKey, value: PropVariant) {
+        let mut writer = Cursor::new(Vec::new());
+        write_prop_variant(&mut writer, &value).expect("writing into a buffer must not fail");
+        self.raw_values.push((key.clone(), writer.into_inner()));
+        self.properties.push((key, value));
+    }
+}
+
+/// The key of a property in a [`PropertyStore`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum PropertyKey {
+    /// a property which is identified by a numeric id
+    Integer(u32),
+
+    /// a property which is identified by its name. This is only used in
+    /// the property set `D5CDD505-2E9C-101B-9397-08002B2CF9AE`
+    Name(String),
+}
+
+/// The identifier of a property in a [`PropertyStore`]
+pub type PropertyId = PropertyKey;
+
+/// A typed property value ([MS-OLEPS] section 2.15)
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[allow(missing_docs)]
+pub enum PropVariant {
+    Empty,
+    Null,
+    I1(i8),
+    I2(i16),
+    I4(i32),
+    I8(i64),
+    UI1(u8),
+    UI2(u16),
+    UI4(u32),
+    UI8(u64),
+    R4(f32),
+    R8(f64),
+    Bool(bool),
+    LpStr(String),
+    LpWStr(String),
+    FileTime(FileTime),
+    Clsid(Guid),
+
+    /// binary data of an application-defined format (`VT_BLOB`)
+    Blob(Vec<u8>),
+
+    /// the content of a stream (`VT_STREAM`), like a thumbnail image. In a
+    /// serialized property storage, the content is stored like a blob.
+    Stream(Vec<u8>),
+
+    /// a vector of values of the same type (`VT_VECTOR`), like a list of
+    /// keywords
+    Vector(Vec<PropVariant>),
+
+    /// a value of a type which is not supported. Contains the raw bytes of
+    /// the value.
+    Unknown {
+        vartype: u16,
+        data: Vec<u8>,
+    },
+}
+
+impl PropVariant {
+    /// returns the vartype which is used to serialize this value
+    fn vartype(&self) -> u16 {
+        match self {
+            Self::Empty => 0x0000,
+            Self::Null => 0x0001,
+            Self::I2(_) => 0x0002,
+            Self::I4(_) => 0x0003,
+            Self::R4(_) => 0x0004,
+            Self::R8(_) => 0x0005,
+            Self::Bool(_) => 0x000b,
+            Self::I1(_) => 0x0010,
+            Self::UI1(_) => 0x0011,
+            Self::UI2(_) => 0x0012,
+            Self::UI4(_) => 0x0013,
+            Self::I8(_) => 0x0014,
+            Self::UI8(_) => 0x0015,
+            Self::LpStr(_) => 0x001e,
+            Self::LpWStr(_) => 0x001f,
+            Self::FileTime(_) => 0x0040,
+            Self::Blob(_) => 0x0041,
+            Self::Stream(_) => 0x0042,
+            Self::Clsid(_) => 0x0048,
+            Self::Vector(_) => VT_VECTOR,
+            Self::Unknown { vartype, .. } => *vartype,
+        }
+    }
+
+    /// returns the value as string slice, if it is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::LpStr(s) | Self::LpWStr(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// returns the value as byte slice, if it is a blob or a stream
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Blob(data) | Self::Stream(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// Creates a [`PropertyStoreDataBlock`] from typed property values, e.g. to
+/// store the AppUserModelID of a new link.
+///
+/// ```
+/// use lnk::{
+///     extradata::property_store_data::{PropVariant, PropertyKey, PropertyStoreBuilder},
+///     Guid,
+/// };
+///
+/// let format_id = Guid::from(uuid::uuid!("9F4C2855-9F79-4B39-A8D0-E1D42DE1D5F3"));
+/// let block = PropertyStoreBuilder::new()
+///     .property(
+///         format_id,
+///         PropertyKey::Integer(5),
+///         PropVariant::LpWStr("Contoso.Editor".to_string()),
+///     )
+///     .build();
+/// assert_eq!(
+///     block
+///         .get(&format_id, &PropertyKey::Integer(5))
+///         .and_then(PropVariant::as_str),
+///     Some("Contoso.Editor")
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PropertyStoreBuilder {
+    properties: Vec<(Guid, PropertyKey, PropVariant)>,
+}
+
+impl PropertyStoreBuilder {
+    /// creates a new builder, which has no properties
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sets the value of the property identified by `format_id` and `key`,
+    /// replacing an existing value. Properties which are identified by name
+    /// can only be stored in the property set
+    /// `D5CDD505-2E9C-101B-9397-08002B2CF9AE`.
+    pub fn property(mut self, format_id: Guid, key: PropertyKey, value: PropVariant) -> Self {
+        match self
+            .properties
+            .iter_mut()
+            .find(|(f, k, _)| *f == format_id && *k == key)
+        {
+            Some((_, _, existing)) => *existing = value,
+            None => self.properties.push((format_id, key, value)),
+        }
+        self
+    }
+
+    /// creates the [`PropertyStoreDataBlock`], which stores the properties
+    /// of each property set in a serialized property storage structure.
+    /// Values of type [`PropVariant::LpStr`] are encoded using Windows-1252.
+    pub fn build(self) -> PropertyStoreDataBlock {
+        let mut property_stores: Vec<PropertyStore> = Vec::new();
+        for (format_id, key, value) in self.properties {
+            match property_stores
+                .iter_mut()
+                .find(|store| store.format_id == format_id)
+            {
+                Some(store) => store.push(key, value),
+                None => {
+                    let mut store = PropertyStore {
+                        format_id,
+                        properties: Vec::new(),
+                        raw_values: Vec::new(),
+                    };
+                    store.push(key, value);
+                    property_stores.push(store);
+                }
+            }
+        }
+
+        let mut writer = Cursor::new(Vec::new());
+        for store in &property_stores {
+            write_property_store(&mut writer, store).expect("writing into a buffer must not fail");
+        }
+        writer
+            .write_le(&0u32)
+            .expect("writing into a buffer must not fail");
+
+        PropertyStoreDataBlock {
+            property_store: writer.into_inner(),
+            property_stores,
+        }
+    }
+}
+
+impl From<&PropertyStoreDataBlock> for PropertyStoreBuilder {
+    /// creates a builder which contains all properties of `block`
+    fn from(block: &PropertyStoreDataBlock) -> Self {
+        let properties = block
+            .property_stores
+            .iter()
+            .flat_map(|store| {
+                store
+                    .properties
+                    .iter()
+                    .map(|(key, value)| (store.format_id, key.clone(), value.clone()))
+            })
+            .collect();
+        Self { properties }
+    }
+}
+
+/// the property set of `System.AppUserModel.*`
+pub(crate) const APP_USER_MODEL: uuid::Uuid = uuid::uuid!("9F4C2855-9F79-4B39-A8D0-E1D42DE1D5F3");
+
+/// the property id of `System.AppUserModel.ID`
+pub(crate) const APP_USER_MODEL_ID: u32 = 5;
+
+/// the version of a serialized property storage structure, which is `1SPS`
+const PROPERTY_STORAGE_VERSION: u32 = 0x5350_5331;
+
+/// the property set whose properties are identified by name instead of an
+/// integer id
+const NAMED_PROPERTIES_FORMAT_ID: uuid::Uuid = uuid::uuid!("D5CDD505-2E9C-101B-9397-08002B2CF9AE");
+
+fn parse_property_stores(data: &[u8], default_codepage: &'static Encoding) -> Vec<PropertyStore> {
+    let mut reader = Cursor::new(data);
+    let mut stores = Vec::new();
+    while (reader.position() as usize) < data.len() {
+        match read_property_store(&mut reader, data.len() as u64, default_codepage) {
+            Ok(Some(store)) => stores.push(store),
+            Ok(None) => break,
+            Err(why) => {
+                warn!("unable to parse serialized property storage: {why}");
+                break;
+            }
+        }
+    }
+    stores
+}
+
+fn read_property_store<R: Read + Seek>(
+    reader: &mut R,
+    end: u64,
+    default_codepage: &'static Encoding,
+) -> BinResult<Option<PropertyStore>> {
+    let start = reader.stream_position()?;
+    let storage_size: u32 = reader.read_le()?;
+    if storage_size == 0 {
+        return Ok(None);
+    }
+
+    let version: u32 = reader.read_le()?;
+    if version != PROPERTY_STORAGE_VERSION {
+        return Err(binrw::Error::BadMagic {
+            pos: start + 4,
+            found: Box::new(version),
+        });
+    }
+
+    let format_id: Guid = reader.read_le()?;
+    let is_named = format_id == Guid::from(NAMED_PROPERTIES_FORMAT_ID);
+
+    let mut properties = Vec::new();
+    let mut raw_values = Vec::new();
+    loop {
+        let value_start = reader.stream_position()?;
+        let value_size: u32 = reader.read_le()?;
+        if value_size == 0 {
+            break;
+        }
+        let value_end = value_start + u64::from(value_size);
+        if value_end > end {
+            return Err(binrw::Error::AssertFail {
+                pos: value_start,
+                message: format!("value size {value_size} exceeds the property storage"),
+            });
+        }
+
+        let key = if is_named {
+            let name_size: u32 = reader.read_le()?;
+            let _reserved: u8 = reader.read_le()?;
+            let buffer = read_bytes(reader, u64::from(name_size), value_end)?;
+            let (name, _, _) = UTF_16LE.decode(&buffer);
+            PropertyKey::Name(name.trim_end_matches('\0').to_string())
+        } else {
+            let id: u32 = reader.read_le()?;
+            let _reserved: u8 = reader.read_le()?;
+            PropertyKey::Integer(id)
+        };
+
+        let position = reader.stream_position()?;
+        let value = read_prop_variant(reader, value_end, default_codepage)?;
+        trace!("read property {key:?}: {value:?}");
+        reader.seek(SeekFrom::Start(position))?;
+        let raw_value = read_bytes(reader, value_end.saturating_sub(position), value_end)?;
+        raw_values.push((key.clone(), raw_value));
+        properties.push((key, value));
+    }
+
+    reader.seek(SeekFrom::Start(start + u64::from(storage_size)))?;
+    Ok(Some(PropertyStore {
+        format_id,
+        properties,
+        raw_values,
+    }))
+}
+
+/// the flag of a vartype which marks a vector of values of the remaining type
+const VT_VECTOR: u16 = 0x1000;
+
+fn read_prop_variant<R: Read + Seek>(
+    reader: &mut R,
+    value_end: u64,
+    default_codepage: &'static Encoding,
+) -> BinResult<PropVariant> {
+    let vartype: u16 = reader.read_le()?;
+    let _padding: u16 = reader.read_le()?;
+
+    let position = reader.stream_position()?;
+    let value = if vartype & VT_VECTOR != 0 {
+        read_vector(reader, vartype & !VT_VECTOR, value_end, default_codepage)?
+    } else {
+        read_scalar(reader, vartype, value_end, default_codepage)?
+    };
+    match value {
+        Some(value) => Ok(value),
+        None => {
+            reader.seek(SeekFrom::Start(position))?;
+            let data = read_bytes(reader, value_end.saturating_sub(position), value_end)?;
+            Ok(PropVariant::Unknown { vartype, data })
+        }
+    }
+}
+
+/// reads the elements of a vector, which consists of the number of elements,
+/// followed by the elements. Strings are padded to a multiple of 4 bytes.
+/// Returns `None` if the type of the elements is not supported.
+fn read_vector<R: Read + Seek>(
+    reader: &mut R,
+    vartype: u16,
+    value_end: u64,
+    default_codepage: &'static Encoding,
+) -> BinResult<Option<PropVariant>> {
+    let position = reader.stream_position()?;
+    if matches!(vartype, 0x0000 | 0x0001) {
+        return Err(binrw::Error::AssertFail {
+            pos: position,
+            message: format!("vector of zero-sized elements (vartype {vartype:#06x})"),
+        });
+    }
+
+    // every element occupies at least one byte
+    let count: u32 = reader.read_le()?;
+    if u64::from(count) > value_end.saturating_sub(position + 4) {
+        return Err(binrw::Error::AssertFail {
+            pos: position,
+            message: format!("vector of {count} elements exceeds the property value"),
+        });
+    }
+    let mut elements = Vec::new();
+    for _ in 0..count {
+        let start = reader.stream_position()?;
+        match read_scalar(reader, vartype, value_end, default_codepage)? {
+            Some(element) => elements.push(element),
+            None => return Ok(None),
+        }
+        if matches!(vartype, 0x001e | 0x001f) {
+            let size = reader.stream_position()? - start;
+            reader.seek(SeekFrom::Current(((4 - size % 4) % 4) as i64))?;
+        }
+    }
+    Ok(Some(PropVariant::Vector(elements)))
+}
+
+/// reads a single value of type `vartype`. Returns `None` if the type is not
+/// supported.
+fn read_scalar<R: Read + Seek>(
+    reader: &mut R,
+    vartype: u16,
+    value_end: u64,
+    default_codepage: &'static Encoding,
+) -> BinResult<Option<PropVariant>> {
+    Ok(Some(match vartype {
+        0x0000 => PropVariant::Empty,
+        0x0001 => PropVariant::Null,
+        0x0002 => PropVariant::I2(reader.read_le()?),
+        0x0003 | 0x0016 => PropVariant::I4(reader.read_le()?),
+        0x0004 => PropVariant::R4(reader.read_le()?),
+        0x0005 => PropVariant::R8(reader.read_le()?),
+        0x000b => PropVariant::Bool(reader.read_le::<u16>()? != 0),
+        0x0010 => PropVariant::I1(reader.read_le()?),
+        0x0011 => PropVariant::UI1(reader.read_le()?),
+        0x0012 => PropVariant::UI2(reader.read_le()?),
+        0x0013 | 0x0017 => PropVariant::UI4(reader.read_le()?),
+        0x0014 => PropVariant::I8(reader.read_le()?),
+        0x0015 => PropVariant::UI8(reader.read_le()?),
+        0x001e => {
+            let size: u32 = reader.read_le()?;
+            let buffer = read_bytes(reader, u64::from(size), value_end)?;
+            let (s, _, _) = default_codepage.decode(&buffer);
+            PropVariant::LpStr(s.trim_end_matches('\0').to_string())
+        }
+        0x001f => {
+            let length: u32 = reader.read_le()?;
+            let buffer = read_bytes(reader, 2 * u64::from(length), value_end)?;
+            let (s, _, _) = UTF_16LE.decode(&buffer);
+            PropVariant::LpWStr(s.trim_end_matches('\0').to_string())
+        }
+        0x0040 => PropVariant::FileTime(reader.read_le()?),
+        0x0041 | 0x0042 => {
+            let size: u32 = reader.read_le()?;
+            let data = read_bytes(reader, u64::from(size), value_end)?;
+            if vartype == 0x0041 {
+                PropVariant::Blob(data)
+            } else {
+                PropVariant::Stream(data)
+            }
+        }
+        0x0048 => PropVariant::Clsid(reader.read_le()?),
+        _ => return Ok(None),
+    }))
+}
+
+/// reads `size` bytes, which must not exceed `value_end`. This prevents
+/// allocating a buffer of an arbitrary size read from the file.
+fn read_bytes<R: Read + Seek>(reader: &mut R, size: u64, value_end: u64) -> BinResult<Vec<u8>> {
+    let position = reader.stream_position()?;
+    if position.saturating_add(size) > value_end {
+        return Err(binrw::Error::AssertFail {
+            pos: position,
+            message: format!("size {size} exceeds the property value"),
+        });
+    }
+    let mut buffer = vec![0; size as usize];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn write_property_store<W: Write + Seek>(writer: &mut W, store: &PropertyStore) -> BinResult<()> {
+    let start = writer.stream_position()?;
+    writer.write_le(&0u32)?;
+    writer.write_le(&PROPERTY_STORAGE_VERSION)?;
+    writer.write_le(&store.format_id)?;
+
+    for (key, value) in &store.properties {
+        let value_start = writer.stream_position()?;
+        writer.write_le(&0u32)?;
+        match key {
+            PropertyKey::Name(name) => {
+                let name: Vec<u8> = name
+                    .encode_utf16()
+                    .chain([0])
+                    .flat_map(|c| c.to_le_bytes())
+                    .collect();
+                writer.write_le(&(name.len() as u32))?;
+                writer.write_le(&0u8)?;
+                writer.write_all(&name)?;
+            }
+            PropertyKey::Integer(id) => {
+                writer.write_le(id)?;
+                writer.write_le(&0u8)?;
+            }
+        }
+        write_prop_variant(writer, value)?;
+        write_size(writer, value_start)?;
+    }
+    writer.write_le(&0u32)?;
+    write_size(writer, start)
+}
+
+/// writes the number of bytes since `start` into the size field at `start`
+fn write_size<W: Write + Seek>(writer: &mut W, start: u64) -> BinResult<()> {
+    let end = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(start))?;
+    writer.write_le(&((end - start) as u32))?;
+    writer.seek(SeekFrom::Start(end))?;
+    Ok(())
+}
+
+/// writes a typed property value, which is padded to a multiple of 4 bytes
+fn write_prop_variant<W: Write + Seek>(writer: &mut W, value: &PropVariant) -> BinResult<()> {
+    let start = writer.stream_position()?;
+    let vartype = match value {
+        PropVariant::Vector(elements) => {
+            VT_VECTOR | elements.first().map_or(0x0000, PropVariant::vartype)
+        }
+        value => value.vartype(),
+    };
+    writer.write_le(&vartype)?;
+    writer.write_le(&0u16)?;
+
+    match value {
+        PropVariant::Vector(elements) => {
+            writer.write_le(&(elements.len() as u32))?;
+            for element in elements {
+                let element_start = writer.stream_position()?;
+                write_scalar(writer, element)?;
+                if matches!(element, PropVariant::LpStr(_) | PropVariant::LpWStr(_)) {
+                    write_padding(writer, element_start)?;
+                }
+            }
+        }
+        value => write_scalar(writer, value)?,
+    }
+    write_padding(writer, start)
+}
+
+/// pads the data written since `start` to a multiple of 4 bytes
+fn write_padding<W: Write + Seek>(writer: &mut W, start: u64) -> BinResult<()> {
+    let size = writer.stream_position()? - start;
+    writer.write_all(&vec![0; ((4 - size % 4) % 4) as usize])?;
+    Ok(())
+}
+
+fn write_scalar<W: Write + Seek>(writer: &mut W, value: &PropVariant) -> BinResult<()> {
+    match value {
+        PropVariant::Empty | PropVariant::Null | PropVariant::Vector(_) => (),
+        PropVariant::I1(v) => writer.write_le(v)?,
+        PropVariant::I2(v) => writer.write_le(v)?,
+        PropVariant::I4(v) => writer.write_le(v)?,
+        PropVariant::I8(v) => writer.write_le(v)?,
+        PropVariant::UI1(v) => writer.write_le(v)?,
+        PropVariant::UI2(v) => writer.write_le(v)?,
+        PropVariant::UI4(v) => writer.write_le(v)?,
+        PropVariant::UI8(v) => writer.write_le(v)?,
+        PropVariant::R4(v) => writer.write_le(v)?,
+        PropVariant::R8(v) => writer.write_le(v)?,
+        PropVariant::Bool(v) => writer.write_le(&if *v { 0xffffu16 } else { 0 })?,
+        PropVariant::LpStr(s) => {
+            let (bytes, _, _) = WINDOWS_1252.encode(s);
+            writer.write_le(&(bytes.len() as u32 + 1))?;
+            writer.write_all(&bytes)?;
+            writer.write_le(&0u8)?;
+        }
+        PropVariant::LpWStr(s) => {
+            let chars: Vec<u16> = s.encode_utf16().chain([0]).collect();
+            writer.write_le(&(chars.len() as u32))?;
+            writer.write_le(&chars)?;
+        }
+        PropVariant::FileTime(v) => writer.write_le(v)?,
+        PropVariant::Clsid(v) => writer.write_le(v)?,
+        PropVariant::Blob(data) | PropVariant::Stream(data) => {
+            writer.write_le(&(data.len() as u32))?;
+            writer.write_all(data)?;
+        }
+        PropVariant::Unknown { data, .. } => writer.write_all(data)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::WINDOWS_1252;
+
+    use super::*;
+
+    /// encodes `value` as UnicodeString, without padding
+    fn unicode_string(value: &str) -> Vec<u8> {
+        let value: Vec<u8> = value
+            .encode_utf16()
+            .chain([0])
+            .flat_map(|c| c.to_le_bytes())
+            .collect();
+        [(value.len() as u32 / 2).to_le_bytes().to_vec(), value].concat()
+    }
+
+    fn property(id: u32, vartype: u32, value: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(13 + value.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&id.to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&vartype.to_le_bytes());
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    fn integer_property(id: u32, value: &str) -> Vec<u8> {
+        property(id, 0x1f, &unicode_string(value))
+    }
+
+    fn property_store(properties: &[Vec<u8>]) -> Vec<u8> {
+        let properties = [properties.concat(), 0u32.to_le_bytes().to_vec()].concat();
+        let mut data = Vec::new();
+        data.extend_from_slice(&(24 + properties.len() as u32).to_le_bytes());
+        data.extend_from_slice(&PROPERTY_STORAGE_VERSION.to_le_bytes());
+        data.extend_from_slice(&APP_USER_MODEL.to_bytes_le());
+        data.extend_from_slice(&properties);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_integer_keys() {
+        let data = property_store(&[integer_property(5, "Microsoft.Windows.PowerShell")]);
+
+        let stores = parse_property_stores(&data, WINDOWS_1252);
+        assert_eq!(stores.len(), 1);
+        assert_eq!(stores[0].format_id(), &Guid::from(APP_USER_MODEL));
+
+        let block = PropertyStoreDataBlock {
+            property_store: data,
+            property_stores: stores,
+        };
+        assert_eq!(
+            block.app_user_model_id().as_deref(),
+            Some("Microsoft.Windows.PowerShell")
+        );
+        assert!(block
+            .get(&Guid::from(APP_USER_MODEL), &PropertyKey::Integer(4))
+            .is_none());
+
+        let value = [
+            0x1fu32.to_le_bytes().to_vec(),
+            unicode_string("Microsoft.Windows.PowerShell"),
+        ];
+        assert_eq!(
+            block.property_stores()[0].raw_values(),
+            &[(PropertyKey::Integer(5), value.concat())]
+        );
+    }
+
+    #[test]
+    fn test_vector() {
+        // a VT_VECTOR | VT_LPWSTR value, whose strings are padded to a
+        // multiple of 4 bytes
+        let mut value = 2u32.to_le_bytes().to_vec();
+        for keyword in ["lnk", "shortcut"] {
+            let mut string = unicode_string(keyword);
+            string.resize(string.len().next_multiple_of(4), 0);
+            value.extend_from_slice(&string);
+        }
+        let data = property_store(&[property(6, 0x101f, &value)]);
+
+        let stores = parse_property_stores(&data, WINDOWS_1252);
+        assert_eq!(stores.len(), 1);
+        let (key, value) = &stores[0].properties()[0];
+        assert_eq!(key, &PropertyKey::Integer(6));
+        let PropVariant::Vector(elements) = value else {
+            panic!("unexpected value: {value:?}");
+        };
+        let strings: Vec<_> = elements.iter().filter_map(PropVariant::as_str).collect();
+        assert_eq!(strings, ["lnk", "shortcut"]);
+    }
+
+    #[test]
+    fn test_blob() {
+        let mut value = 5u32.to_le_bytes().to_vec();
+        value.extend_from_slice(b"\x89PNG\0\0\0");
+        let data = property_store(&[property(27, 0x42, &value), property(28, 0x41, &value)]);
+
+        let stores = parse_property_stores(&data, WINDOWS_1252);
+        assert!(matches!(
+            &stores[0].properties()[..],
+            [(_, PropVariant::Stream(a)), (_, PropVariant::Blob(b))]
+                if a == b"\x89PNG\0" && b == b"\x89PNG\0"
+        ));
+    }
+
+    #[test]
+    fn test_implausible_sizes() {
+        // a VT_VECTOR | VT_EMPTY value with 0xFFFFFFFF elements of 0 bytes
+        let data = property_store(&[property(6, 0x1000, &u32::MAX.to_le_bytes())]);
+        assert!(parse_property_stores(&data, WINDOWS_1252).is_empty());
+
+        // a vector, whose count exceeds the size of the value
+        let data = property_store(&[property(6, 0x1013, &u32::MAX.to_le_bytes())]);
+        assert!(parse_property_stores(&data, WINDOWS_1252).is_empty());
+
+        // strings and blobs, whose size exceeds the size of the value
+        for vartype in [0x1e, 0x1f, 0x41, 0x42] {
+            let data = property_store(&[property(6, vartype, &u32::MAX.to_le_bytes())]);
+            assert!(parse_property_stores(&data, WINDOWS_1252).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_builder_round_trip() {
+        let named = Guid::from(NAMED_PROPERTIES_FORMAT_ID);
+        let keywords = PropVariant::Vector(vec![
+            PropVariant::LpWStr("lnk".to_string()),
+            PropVariant::LpWStr("shortcut".to_string()),
+        ]);
+        let block = PropertyStoreBuilder::new()
+            .property(
+                named,
+                PropertyKey::Name("Answer".to_string()),
+                PropVariant::I4(42),
+            )
+            .property(
+                Guid::from(APP_USER_MODEL),
+                PropertyKey::Integer(6),
+                keywords,
+            )
+            .property(
+                named,
+                PropertyKey::Name("Answer".to_string()),
+                PropVariant::UI2(43),
+            )
+            .build();
+
+        let stores = parse_property_stores(block.property_store(), WINDOWS_1252);
+        assert_eq!(stores.len(), 2);
+        assert!(matches!(
+            stores[0].properties()[..],
+            [(PropertyKey::Name(ref name), PropVariant::UI2(43))] if name == "Answer"
+        ));
+        let PropVariant::Vector(elements) = &stores[1].properties()[0].1 else {
+            panic!("unexpected properties: {:?}", stores[1].properties());
+        };
+        let strings: Vec<_> = elements.iter().filter_map(PropVariant::as_str).collect();
+        assert_eq!(strings, ["lnk", "shortcut"]);
+
+        for (built, parsed) in block.property_stores().iter().zip(&stores) {
+            assert_eq!(built.raw_values(), parsed.raw_values());
+        }
     }
 }
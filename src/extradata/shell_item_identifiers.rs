@@ -1,5 +1,6 @@
 use binrw::BinRead;
 use getset::Getters;
+#[cfg(feature = "serde")]
 use serde::Serialize;
 
 use crate::generic_types::idlist::IdList;
@@ -7,11 +8,23 @@ use crate::generic_types::idlist::IdList;
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size != 10))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 /// Contains a list of item identifiers.
 /// <https://learn.microsoft.com/en-us/windows/win32/api/shtypes/ns-shtypes-itemidlist>
 pub struct ShellItemIdentifiers {
-    #[br(args((block_size - 8).try_into().unwrap()))]
+    #[br(args((block_size - 8).try_into().unwrap(), usize::MAX))]
     items: IdList,
 }
+
+impl ShellItemIdentifiers {
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self) -> binrw::BinResult<Vec<u8>> {
+        use binrw::BinWriterExt;
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        cursor.write_le(&self.items)?;
+        Ok(cursor.into_inner())
+    }
+}
@@ -7,6 +7,8 @@ use getset::Getters;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+#[cfg(feature = "binwrite")]
+use crate::strings::fixed_size_bytes;
 use crate::strings::FixedSizeString;
 
 /// The ShimDataBlock structure specifies the name of a shim that can
@@ -14,7 +16,7 @@ use crate::strings::FixedSizeString;
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size >= 0x0000_00088))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct ShimDataBlock {
     /// A Unicode string that specifies the name of a shim layer to apply
@@ -22,3 +24,13 @@ pub struct ShimDataBlock {
     #[br(args(usize::try_from(block_size).unwrap() - 2*size_of::<u32>(), UTF_16LE), map=|s:FixedSizeString| s.to_string())]
     layer_name: String,
 }
+
+impl ShimDataBlock {
+    /// serializes this block, without its size and signature. The layer name
+    /// is padded with NULs to the minimum size of the block.
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let size = (2 * self.layer_name.encode_utf16().count() + 2).max(0x88 - 8);
+        fixed_size_bytes(&self.layer_name, size, UTF_16LE)
+    }
+}
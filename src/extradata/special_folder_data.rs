@@ -4,6 +4,8 @@ use getset::Getters;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+use crate::IdList;
+
 /// The SpecialFolderDataBlock structure specifies the location of a
 /// special folder. This data can be used when a link target is a
 /// special folder to keep track of the folder, so that the link target
@@ -11,7 +13,7 @@ use serde::Serialize;
 #[derive(Clone, Copy, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size == 0x0000_00010))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct SpecialFolderDataBlock {
     /// A 32-bit, unsigned integer that specifies the folder integer ID.
@@ -22,3 +24,80 @@ pub struct SpecialFolderDataBlock {
     /// link target IDList.
     offset: u32,
 }
+
+/// the names of the most common CSIDL values
+#[rustfmt::skip]
+const CSIDL_NAMES: [(u32, &str); 42] = [
+    (0x00, "CSIDL_DESKTOP"),
+    (0x01, "CSIDL_INTERNET"),
+    (0x02, "CSIDL_PROGRAMS"),
+    (0x03, "CSIDL_CONTROLS"),
+    (0x04, "CSIDL_PRINTERS"),
+    (0x05, "CSIDL_PERSONAL"),
+    (0x06, "CSIDL_FAVORITES"),
+    (0x07, "CSIDL_STARTUP"),
+    (0x08, "CSIDL_RECENT"),
+    (0x09, "CSIDL_SENDTO"),
+    (0x0a, "CSIDL_BITBUCKET"),
+    (0x0b, "CSIDL_STARTMENU"),
+    (0x0d, "CSIDL_MYMUSIC"),
+    (0x0e, "CSIDL_MYVIDEO"),
+    (0x10, "CSIDL_DESKTOPDIRECTORY"),
+    (0x11, "CSIDL_DRIVES"),
+    (0x12, "CSIDL_NETWORK"),
+    (0x13, "CSIDL_NETHOOD"),
+    (0x14, "CSIDL_FONTS"),
+    (0x15, "CSIDL_TEMPLATES"),
+    (0x16, "CSIDL_COMMON_STARTMENU"),
+    (0x17, "CSIDL_COMMON_PROGRAMS"),
+    (0x18, "CSIDL_COMMON_STARTUP"),
+    (0x19, "CSIDL_COMMON_DESKTOPDIRECTORY"),
+    (0x1a, "CSIDL_APPDATA"),
+    (0x1b, "CSIDL_PRINTHOOD"),
+    (0x1c, "CSIDL_LOCAL_APPDATA"),
+    (0x20, "CSIDL_INTERNET_CACHE"),
+    (0x21, "CSIDL_COOKIES"),
+    (0x22, "CSIDL_HISTORY"),
+    (0x23, "CSIDL_COMMON_APPDATA"),
+    (0x24, "CSIDL_WINDOWS"),
+    (0x25, "CSIDL_SYSTEM"),
+    (0x26, "CSIDL_PROGRAM_FILES"),
+    (0x27, "CSIDL_MYPICTURES"),
+    (0x28, "CSIDL_PROFILE"),
+    (0x29, "CSIDL_SYSTEMX86"),
+    (0x2a, "CSIDL_PROGRAM_FILESX86"),
+    (0x2b, "CSIDL_PROGRAM_FILES_COMMON"),
+    (0x2e, "CSIDL_COMMON_DOCUMENTS"),
+    (0x2f, "CSIDL_COMMON_ADMINTOOLS"),
+    (0x30, "CSIDL_ADMINTOOLS"),
+];
+
+impl SpecialFolderDataBlock {
+    /// returns the name of the CSIDL constant of the special folder, like
+    /// `CSIDL_SYSTEM`. The flags in the upper bits of the folder id are
+    /// ignored. Only the most common folders are supported.
+    pub fn csidl_name(&self) -> Option<&'static str> {
+        let csidl = self.special_folder_id & 0xff;
+        CSIDL_NAMES
+            .iter()
+            .find(|(id, _)| *id == csidl)
+            .map(|(_, name)| *name)
+    }
+
+    /// returns the index of the ItemID in `id_list` which [`Self::offset`]
+    /// points to, i.e. the first item which is relative to the special
+    /// folder. If the offset points to the end of the list, the length of the
+    /// list is returned. Returns `None` if the offset does not point to the
+    /// start of an ItemID of the list.
+    pub fn item_index(&self, id_list: &IdList) -> Option<usize> {
+        id_list.item_index(self.offset)
+    }
+
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = self.special_folder_id.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.offset.to_le_bytes());
+        bytes
+    }
+}
@@ -1,18 +1,22 @@
 use binrw::BinRead;
 use encoding_rs::Encoding;
+use getset::Getters;
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+#[cfg(feature = "binwrite")]
+use crate::strings::fixed_size_bytes;
 use crate::{strings::FixedSizeString, Guid};
 
 /// The TrackerDataBlock structure specifies data that can be used to
 /// resolve a link target if it is not found in its original location
 /// when the link is resolved. This data is passed to the Link
 /// Tracking service [MS-DLTW] to find the link target.
-#[derive(Clone, Debug, BinRead)]
+#[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32, default_codepage: &'static Encoding), pre_assert(block_size == 0x0000_00060))]
+#[getset(get = "pub")]
 #[allow(unused)]
 pub struct TrackerDataBlock {
     /// A 32-bit, unsigned integer that specifies the size of the rest of the
@@ -38,3 +42,37 @@ pub struct TrackerDataBlock {
     /// link target with the Link Tracking service
     droid_birth: [Guid; 2],
 }
+
+impl TrackerDataBlock {
+    /// returns the MAC address of the machine where the link target was
+    /// created, as it is encoded in the node field of the object ids. Only
+    /// time-based (version 1) UUIDs contain a MAC address, so `None` is
+    /// returned if neither the current nor the birth object id is one.
+    pub fn mac_address(&self) -> Option<[u8; 6]> {
+        [self.droid[1], self.droid_birth[1]]
+            .into_iter()
+            .find_map(|guid| uuid::Uuid::from(guid).get_node_id())
+    }
+
+    /// serializes this block, without its size and signature. The machine id
+    /// is encoded using `default_codepage`.
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self, default_codepage: &'static Encoding) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(0x58);
+        bytes.extend_from_slice(&self.length.to_le_bytes());
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend(fixed_size_bytes(&self.machine_id, 16, default_codepage));
+        for guid in self.droid.iter().chain(&self.droid_birth) {
+            bytes.extend_from_slice(&uuid::Uuid::from(*guid).to_bytes_le());
+        }
+        bytes
+    }
+
+    /// resets the machine id and all object ids
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn clear(&mut self) {
+        self.machine_id.clear();
+        self.droid = Default::default();
+        self.droid_birth = Default::default();
+    }
+}
@@ -14,10 +14,21 @@ use crate::IdList;
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(import(block_size: u32), pre_assert(block_size >= 0x0000_0000A))]
-#[get(get = "pub")]
-#[allow(unused)]
+#[getset(get = "pub")]
 pub struct VistaAndAboveIdListDataBlock {
-    /// An IDList structure (section 2.2.1).
-    #[br(args(u16::try_from(block_size).unwrap() - u16::try_from(2*size_of::<u32>()).unwrap()))]
+    /// returns the IDList structure (section 2.2.1).
+    #[br(args(u16::try_from(block_size).unwrap() - u16::try_from(2*size_of::<u32>()).unwrap(), usize::MAX))]
     id_list: IdList,
 }
+
+impl VistaAndAboveIdListDataBlock {
+    /// serializes this block, without its size and signature
+    #[cfg(feature = "binwrite")]
+    pub(crate) fn to_bytes(&self) -> binrw::BinResult<Vec<u8>> {
+        use binrw::BinWriterExt;
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        cursor.write_le(&self.id_list)?;
+        Ok(cursor.into_inner())
+    }
+}
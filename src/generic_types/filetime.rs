@@ -1,7 +1,9 @@
 use std::fmt;
 
 use binrw::{BinRead, BinReaderExt, BinWrite};
-use chrono::NaiveDateTime;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use chrono::{NaiveDate, NaiveDateTime};
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
@@ -69,6 +71,27 @@ impl FileTime {
         self.0.to_datetime().naive_utc()
     }
 
+    /// returns `true` if this time is not set, i.e. if it is zero
+    pub fn is_zero(&self) -> bool {
+        self.1 == 0
+    }
+
+    /// converts this time into a UTC timestamp, or returns `None` if it is
+    /// not set. Every non-zero value can be represented, up to the year
+    /// 60056.
+    #[cfg(feature = "chrono")]
+    pub fn utc_datetime(&self) -> Option<DateTime<Utc>> {
+        /// the number of seconds between 1601-01-01 and 1970-01-01
+        const UNIX_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+        if self.is_zero() {
+            return None;
+        }
+        let secs = (self.1 / 10_000_000) as i64 - UNIX_EPOCH_OFFSET;
+        let nanos = (self.1 % 10_000_000) as u32 * 100;
+        DateTime::from_timestamp(secs, nanos)
+    }
+
     /*
     /// Create a new `FileTime` object representing now.
     pub fn now() -> Self {
@@ -79,22 +102,28 @@ impl FileTime {
 
 impl Default for FileTime {
     fn default() -> Self {
-        let raw = 0u64;
+        Self::from(0u64)
+    }
+}
+
+impl From<u64> for FileTime {
+    fn from(raw: u64) -> Self {
         let timestamp = WinTimestamp::new(&raw.to_le_bytes()).unwrap();
         Self(timestamp, raw)
     }
 }
 
-/*
 impl From<NaiveDateTime> for FileTime {
+    /// converts a UTC timestamp. Timestamps before 1601 are clamped to zero.
     fn from(value: NaiveDateTime) -> Self {
-        let duration = value - Self::epoch();
-        let val = duration.num_microseconds().unwrap() * 10;
-        let val = val as u64;
-        Self::from(val)
+        let epoch = NaiveDate::from_ymd_opt(1601, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let ticks = (value - epoch).num_microseconds().unwrap_or(0).max(0) as u64 * 10;
+        Self::from(ticks)
     }
 }
- */
 
 impl From<FileTime> for u64 {
     fn from(val: FileTime) -> Self {
@@ -107,6 +136,7 @@ mod tests {
     use std::io::Cursor;
 
     use binrw::{BinReaderExt, BinWrite};
+    use chrono::NaiveDate;
     use winstructs::timestamp::WinTimestamp;
 
     use super::FileTime;
@@ -135,9 +165,39 @@ mod tests {
         assert_eq!(input.1, output.1);
     }
 
+    #[test]
+    fn test_from_datetime() {
+        let datetime = NaiveDate::from_ymd_opt(2008, 9, 12)
+            .unwrap()
+            .and_hms_opt(20, 27, 18)
+            .unwrap();
+        let filetime = FileTime::from(datetime);
+        assert_eq!(filetime.datetime(), datetime);
+        assert!(!filetime.is_zero());
+        assert!(FileTime::default().is_zero());
+    }
+
     fn test_data() -> FileTime {
         let raw = 123456789000u64;
         let timestamp = WinTimestamp::new(&raw.to_le_bytes()).unwrap();
         FileTime(timestamp, raw)
     }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_utc_datetime() {
+        use chrono::Datelike;
+
+        assert_eq!(FileTime::from(0).utc_datetime(), None);
+
+        // 2009-07-25 23:00:00.1 UTC
+        let datetime = FileTime::from(128_930_364_001_000_000).utc_datetime();
+        assert_eq!(
+            datetime.map(|datetime| datetime.to_rfc3339()),
+            Some("2009-07-25T23:00:00.100+00:00".to_string())
+        );
+
+        let datetime = FileTime::from(u64::MAX).utc_datetime().unwrap();
+        assert_eq!(datetime.year(), 60056);
+    }
 }
@@ -6,15 +6,47 @@ use serde::Serialize;
 use uuid::{Builder, Uuid};
 
 /// wraps a UUID
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Guid(Uuid);
 
+impl Guid {
+    /// parses a GUID in the form Windows renders it, like
+    /// `{00021401-0000-0000-C000-000000000046}`. The braces are optional,
+    /// and the case of the hex digits is ignored. Returns `None` if `s` is
+    /// not a valid GUID.
+    pub fn from_canonical_str(s: &str) -> Option<Self> {
+        let s = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .unwrap_or(s);
+        if s.len() != 36 {
+            return None;
+        }
+        Uuid::try_parse(s).ok().map(Self)
+    }
+
+    /// renders this GUID like Windows does, i.e. in upper case and enclosed
+    /// in braces, like `{00021401-0000-0000-C000-000000000046}`. The fields
+    /// are written in their logical order, which is not the order of the
+    /// bytes on disk: the first three fields are stored in little-endian
+    /// byte order.
+    pub fn to_canonical_string(&self) -> String {
+        format!("{{{}}}", self.0.hyphenated().to_string().to_uppercase())
+    }
+}
+
 impl From<Uuid> for Guid {
     fn from(uuid: Uuid) -> Self {
         Self(uuid)
     }
 }
 
+impl From<Guid> for Uuid {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
 impl BinRead for Guid {
     type Args<'a> = ();
 
@@ -95,6 +127,38 @@ mod tests {
         assert_eq!(input, output);
     }
 
+    #[test]
+    fn test_canonical_string() {
+        // the class id of shell links, as it is stored on disk
+        let bytes = [
+            0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x46,
+        ];
+        let guid: Guid = Cursor::new(bytes).read_le().unwrap();
+        assert_eq!(
+            guid.to_canonical_string(),
+            "{00021401-0000-0000-C000-000000000046}"
+        );
+        assert_eq!(guid.to_string(), "00021401-0000-0000-c000-000000000046");
+
+        assert_eq!(
+            Guid::from_canonical_str("{00021401-0000-0000-C000-000000000046}"),
+            Some(guid)
+        );
+        assert_eq!(
+            Guid::from_canonical_str("00021401-0000-0000-c000-000000000046"),
+            Some(guid)
+        );
+        assert_eq!(
+            Guid::from_canonical_str("{00021401-0000-0000-C000-00000000004}"),
+            None
+        );
+        assert_eq!(
+            Guid::from_canonical_str("00021401000000000C000000000000046"),
+            None
+        );
+    }
+
     #[test]
     fn test_guid_le() {
         let mut cursor = Cursor::new([0u8; 16]);
@@ -1,9 +1,16 @@
 use binrw::{BinRead, BinReaderExt};
 use getset::Getters;
 use log::trace;
+#[cfg(feature = "serde")]
 use serde::Serialize;
 
-use crate::itemid::ItemID;
+use crate::{
+    decoded_segment::{
+        segments_to_path, segments_to_shell_path, segments_to_short_path, segments_to_url,
+    },
+    itemid::{ItemID, ItemIdDecoder},
+    DecodedSegment,
+};
 
 /// The stored IDList structure specifies the format of a persisted item ID list.
 #[derive(Clone, Debug, Default, Getters)]
@@ -14,8 +21,114 @@ pub struct IdList {
     item_id_list: Vec<ItemID>,
 }
 
+impl IdList {
+    /// decodes all items of this list
+    pub fn segments(&self) -> Vec<DecodedSegment> {
+        self.item_id_list.iter().map(ItemID::decode).collect()
+    }
+
+    /// decodes all items of this list, trying each of `decoders` in order
+    /// before the built-in decoding of [`ItemID::decode`]
+    pub fn segments_with(&self, decoders: &[&dyn ItemIdDecoder]) -> Vec<DecodedSegment> {
+        self.item_id_list
+            .iter()
+            .map(|item| {
+                decoders
+                    .iter()
+                    .find_map(|decoder| decoder.decode(item))
+                    .unwrap_or_else(|| item.decode())
+            })
+            .collect()
+    }
+
+    /// assembles the path which is described by this list. Returns `None` if
+    /// the items do not describe a path on a volume, on a network share or an
+    /// URI.
+    pub fn to_path(&self) -> Option<String> {
+        segments_to_path(&self.segments())
+    }
+
+    /// assembles the path which is described by this list, like
+    /// [`IdList::to_path`], but decodes the items with `decoders` first. See
+    /// [`IdList::segments_with`]
+    pub fn to_path_with(&self, decoders: &[&dyn ItemIdDecoder]) -> Option<String> {
+        segments_to_path(&self.segments_with(decoders))
+    }
+
+    /// assembles the path which is described by this list, like
+    /// [`IdList::to_path`], but uses the short (8.3) names of the files and
+    /// directories. Entries which have no long name are used as they are.
+    pub fn to_short_path(&self) -> Option<String> {
+        segments_to_short_path(&self.segments())
+    }
+
+    /// assembles the URL which is described by this list, if it refers to an
+    /// item in an URL namespace. Files and directories which follow the URL
+    /// are appended as path segments, and the URL is percent-encoded.
+    pub fn to_url(&self) -> Option<String> {
+        segments_to_url(&self.segments())
+    }
+
+    /// assembles a path in the shell namespace from this list, which is
+    /// always possible, even if [`IdList::to_path`] fails. Root folders are
+    /// written by their class id (e.g. `::{20D04FE0-3AEA-1069-A2D8-08002B30309D}`),
+    /// items which cannot be decoded are omitted. An empty list refers to the
+    /// desktop.
+    pub fn to_shell_path(&self) -> String {
+        segments_to_shell_path(&self.segments())
+    }
+
+    /// returns the index of the ItemID which starts at `offset` bytes into
+    /// this list. If `offset` points to the end of the list, the length of
+    /// the list is returned. Returns `None` if `offset` does not point to the
+    /// start of an ItemID.
+    pub(crate) fn item_index(&self, offset: u32) -> Option<usize> {
+        let mut item_offset = 0u32;
+        for (index, item) in self.item_id_list.iter().enumerate() {
+            if item_offset == offset {
+                return Some(index);
+            }
+            if item_offset > offset {
+                return None;
+            }
+            item_offset += u32::from(*item.size());
+        }
+        (item_offset == offset).then_some(self.item_id_list.len())
+    }
+}
+
+/// writes all items of this list, followed by the TerminalID. The size of
+/// the list is not written, because it depends on the containing structure.
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for IdList {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        self.item_id_list.write_options(writer, endian, args)?;
+        0u16.write_options(writer, endian, args)
+    }
+}
+
+#[cfg(feature = "binwrite")]
+impl IdList {
+    /// the size of this list in bytes, including the TerminalID
+    pub(crate) fn byte_size(&self) -> usize {
+        self.item_id_list
+            .iter()
+            .map(|item| usize::from(*item.size()))
+            .sum::<usize>()
+            + 2
+    }
+}
+
 impl BinRead for IdList {
-    type Args<'a> = (u16,);
+    /// the size of the list in bytes, and the maximum number of items
+    type Args<'a> = (u16, usize);
 
     fn read_options<R: std::io::Read + std::io::Seek>(
         reader: &mut R,
@@ -45,6 +158,13 @@ impl BinRead for IdList {
                 break;
             }
 
+            if item_id_list.len() >= args.1 {
+                return Err(binrw::error::Error::AssertFail {
+                    pos: reader.stream_position()?,
+                    message: format!("IDList contains more than {} items", args.1),
+                });
+            }
+
             bytes_to_read -= item_id.size();
             item_id_list.push(item_id);
         }
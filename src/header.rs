@@ -28,8 +28,10 @@ pub use file_attributes_flags::FileAttributeFlags;
 // #[br(little)]
 #[getset(get = "pub", get_mut = "pub", set = "pub")]
 pub struct ShellLinkHeader {
-    /// The size, in bytes, of this structure. This value MUST be 0x0000004C.
-    #[br(assert(header_size == 0x0000_004c))]
+    /// The size, in bytes, of this structure. This value MUST be 0x0000004C;
+    /// larger values are accepted, and the additional bytes are stored in
+    /// `header_extension`.
+    #[br(assert(header_size >= 0x0000_004c))]
     header_size: u32,
 
     /// This value MUST be 00021401-0000-0000-C000-000000000046.
@@ -68,7 +70,8 @@ pub struct ShellLinkHeader {
     icon_index: i32,
 
     /// A 32-bit unsigned integer that specifies the expected window state of an application
-    /// launched by the link.
+    /// launched by the link. Unknown values are read as
+    /// [`ShowCommand::ShowNormal`].
     show_command: ShowCommand,
 
     /// A HotkeyFlags structure (section 2.1.3) that specifies the keystrokes used to launch the
@@ -90,6 +93,12 @@ pub struct ShellLinkHeader {
     #[br(assert(reserved3 == 0))]
     #[cfg_attr(feature = "serde", serde(skip))]
     reserved3: u32,
+
+    /// Bytes which follow the mandatory fields if `header_size` is larger than
+    /// 0x0000004C
+    #[br(if(header_size > 0x0000_004c), count = header_size - 0x0000_004c)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    header_extension: Option<Vec<u8>>,
 }
 
 impl ShellLinkHeader {
@@ -117,14 +126,15 @@ impl Default for ShellLinkHeader {
             reserved1: 0,
             reserved2: 0,
             reserved3: 0,
+            header_extension: None,
         }
     }
 }
 
 /// The expected window state of an application launched by the link.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive, BinRead, BinWrite)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive, BinWrite)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
-#[brw(repr=u32)]
+#[bw(repr = u32)]
 pub enum ShowCommand {
     /// The application is open and its window is open in a normal fashion.
     ShowNormal = 0x01,
@@ -134,3 +144,125 @@ pub enum ShowCommand {
     /// The application is open, but its window is not shown. It is not given the keyboard focus.
     ShowMinNoActive = 0x07,
 }
+
+impl ShowCommand {
+    /// converts the value of the ShowCommand field. As required by the
+    /// specification, all unknown values are treated as
+    /// [`ShowCommand::ShowNormal`].
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0x03 => Self::ShowMaximized,
+            0x07 => Self::ShowMinNoActive,
+            _ => Self::ShowNormal,
+        }
+    }
+}
+
+impl BinRead for ShowCommand {
+    type Args<'a> = ();
+
+    /// reads the value of the ShowCommand field, see
+    /// [`ShowCommand::from_u32`]
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        u32::read_options(reader, endian, ()).map(Self::from_u32)
+    }
+}
+
+impl std::fmt::Display for ShowCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ShowNormal => write!(f, "Normal window"),
+            Self::ShowMaximized => write!(f, "Maximized"),
+            Self::ShowMinNoActive => write!(f, "Minimized"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinReaderExt;
+
+    use super::*;
+
+    /// returns the bytes of a header of `header_size` bytes with the given
+    /// link flags and all other fields set to their default values
+    fn header_bytes(header_size: u32, link_flags: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&header_size.to_le_bytes());
+        bytes.extend_from_slice(&[
+            0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x46,
+        ]);
+        bytes.extend_from_slice(&link_flags.to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]); // file attributes
+        bytes.extend_from_slice(&[0; 24]); // timestamps
+        bytes.extend_from_slice(&[0; 8]); // file size, icon index
+        bytes.extend_from_slice(&1u32.to_le_bytes()); // show command
+        bytes.extend_from_slice(&[0; 12]); // hotkey, reserved
+        bytes
+    }
+
+    #[test]
+    fn test_header_extension() {
+        let mut bytes = header_bytes(0x54, 0);
+        bytes.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        bytes.extend_from_slice(&[0xff; 4]);
+
+        let mut cursor = Cursor::new(bytes);
+        let header: ShellLinkHeader = cursor.read_le().unwrap();
+        assert_eq!(*header.header_size(), 0x54);
+        assert_eq!(
+            header.header_extension(),
+            &Some(vec![1, 2, 3, 4, 5, 6, 7, 8])
+        );
+        assert_eq!(cursor.position(), 0x54);
+    }
+
+    #[test]
+    fn test_unknown_link_flags() {
+        let bytes = header_bytes(0x4c, 0x8000_0080);
+        let header: ShellLinkHeader = Cursor::new(bytes).read_le().unwrap();
+        assert!(header.link_flags().contains(LinkFlags::IS_UNICODE));
+        assert!(header.link_flags().has_unknown_bits());
+        assert_eq!(header.link_flags().unknown_bits(), 0x8000_0000);
+
+        let bytes = header_bytes(0x4c, 0x0000_0080);
+        let header: ShellLinkHeader = Cursor::new(bytes).read_le().unwrap();
+        assert!(!header.link_flags().has_unknown_bits());
+    }
+
+    #[test]
+    fn test_show_command() {
+        assert_eq!(ShowCommand::from_u32(1), ShowCommand::ShowNormal);
+        assert_eq!(ShowCommand::from_u32(3), ShowCommand::ShowMaximized);
+        assert_eq!(ShowCommand::from_u32(7), ShowCommand::ShowMinNoActive);
+        assert_eq!(ShowCommand::from_u32(5), ShowCommand::ShowNormal);
+
+        assert_eq!(ShowCommand::ShowNormal.to_string(), "Normal window");
+        assert_eq!(ShowCommand::ShowMaximized.to_string(), "Maximized");
+        assert_eq!(ShowCommand::ShowMinNoActive.to_string(), "Minimized");
+
+        // SW_SHOWMINIMIZED is not allowed by the specification
+        let mut bytes = header_bytes(0x4c, 0);
+        bytes[0x3c..0x40].copy_from_slice(&2u32.to_le_bytes());
+        let header: ShellLinkHeader = Cursor::new(bytes).read_le().unwrap();
+        assert_eq!(*header.show_command(), ShowCommand::ShowNormal);
+
+        let show_command: ShowCommand = Cursor::new(3u32.to_le_bytes()).read_le().unwrap();
+        assert_eq!(show_command, ShowCommand::ShowMaximized);
+        assert_eq!(
+            <ShowCommand as num_traits::FromPrimitive>::from_u32(7),
+            Some(ShowCommand::ShowMinNoActive)
+        );
+        assert_eq!(
+            <ShowCommand as num_traits::FromPrimitive>::from_u32(5),
+            None
+        );
+    }
+}
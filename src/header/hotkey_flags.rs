@@ -1,6 +1,11 @@
-use binrw::binrw;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use binrw::{binrw, BinRead, BinWrite};
 use bitflags::bitflags;
-use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
@@ -47,78 +52,223 @@ impl HotkeyFlags {
     }
 }
 
-#[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
-#[binrw]
-#[cfg_attr(feature = "serde", derive(Serialize))]
-/// An 8-bit unsigned integer that specifies a virtual key code that corresponds to a key on the
-/// keyboard.
-#[br(repr = u8)]
-#[bw(repr = u8)]
-pub enum HotkeyKey {
+impl fmt::Display for HotkeyFlags {
+    /// formats the hotkey like Windows does, e.g. as `Ctrl+Shift+F3`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (HotkeyModifiers::HOTKEYF_CONTROL, "Ctrl"),
+            (HotkeyModifiers::HOTKEYF_SHIFT, "Shift"),
+            (HotkeyModifiers::HOTKEYF_ALT, "Alt"),
+        ] {
+            if self.high_byte.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        self.low_byte.fmt(f)
+    }
+}
+
+impl From<HotkeyFlags> for u16 {
+    /// Packs the hotkey into a WORD, as used by the Win32 API: the low byte
+    /// contains the virtual key code, the high byte contains the modifiers
+    fn from(hotkey: HotkeyFlags) -> Self {
+        u16::from_le_bytes([hotkey.low_byte.into(), hotkey.high_byte.bits()])
+    }
+}
+
+impl From<u16> for HotkeyFlags {
+    /// Unpacks a hotkey from a WORD, as used by the Win32 API. Unknown
+    /// virtual key codes are kept as [`HotkeyKey::Raw`], and unknown
+    /// modifier bits are dropped.
+    fn from(word: u16) -> Self {
+        let [low_byte, high_byte] = word.to_le_bytes();
+        Self {
+            low_byte: HotkeyKey::from(low_byte),
+            high_byte: HotkeyModifiers::from_bits_truncate(high_byte),
+        }
+    }
+}
+
+/// defines [`HotkeyKey`] together with its conversions from and to virtual
+/// key codes
+macro_rules! hotkey_keys {
+    ($($key:ident = $code:literal,)*) => {
+        /// An 8-bit unsigned integer that specifies a virtual key code that corresponds to a key
+        /// on the keyboard.
+        #[allow(missing_docs)]
+        #[derive(Clone, Copy, Debug)]
+        #[cfg_attr(feature = "serde", derive(Serialize))]
+        pub enum HotkeyKey {
+            $($key,)*
+            /// A virtual key code which is not modeled by the other variants.
+            /// It is kept as is, so that the hotkey survives a round trip.
+            /// A `Raw` key is equal to the named variant of the same code.
+            Raw(u8),
+        }
+
+        impl HotkeyKey {
+            /// returns the named variant of the virtual key code `code`, or
+            /// `None` if the code is not modeled by a named variant
+            pub fn from_u8(code: u8) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$key),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<u8> for HotkeyKey {
+            /// returns the named variant of the virtual key code `code`, or
+            /// [`HotkeyKey::Raw`] if the code is not modeled
+            fn from(code: u8) -> Self {
+                Self::from_u8(code).unwrap_or(Self::Raw(code))
+            }
+        }
+
+        impl From<HotkeyKey> for u8 {
+            fn from(key: HotkeyKey) -> Self {
+                match key {
+                    $(HotkeyKey::$key => $code,)*
+                    HotkeyKey::Raw(code) => code,
+                }
+            }
+        }
+    };
+}
+
+hotkey_keys! {
     NoKeyAssigned = 0x00,
     Key0 = 0x30,
-    Key1,
-    Key2,
-    Key3,
-    Key4,
-    Key5,
-    Key6,
-    Key7,
-    Key8,
-    Key9,
+    Key1 = 0x31,
+    Key2 = 0x32,
+    Key3 = 0x33,
+    Key4 = 0x34,
+    Key5 = 0x35,
+    Key6 = 0x36,
+    Key7 = 0x37,
+    Key8 = 0x38,
+    Key9 = 0x39,
     KeyA = 0x41,
-    KeyB,
-    KeyC,
-    KeyD,
-    KeyE,
-    KeyF,
-    KeyG,
-    KeyH,
-    KeyI,
-    KeyJ,
-    KeyK,
-    KeyL,
-    KeyM,
-    KeyN,
-    KeyO,
-    KeyP,
-    KeyQ,
-    KeyR,
-    KeyS,
-    KeyT,
-    KeyU,
-    KeyV,
-    KeyW,
-    KeyX,
-    KeyY,
-    KeyZ,
+    KeyB = 0x42,
+    KeyC = 0x43,
+    KeyD = 0x44,
+    KeyE = 0x45,
+    KeyF = 0x46,
+    KeyG = 0x47,
+    KeyH = 0x48,
+    KeyI = 0x49,
+    KeyJ = 0x4a,
+    KeyK = 0x4b,
+    KeyL = 0x4c,
+    KeyM = 0x4d,
+    KeyN = 0x4e,
+    KeyO = 0x4f,
+    KeyP = 0x50,
+    KeyQ = 0x51,
+    KeyR = 0x52,
+    KeyS = 0x53,
+    KeyT = 0x54,
+    KeyU = 0x55,
+    KeyV = 0x56,
+    KeyW = 0x57,
+    KeyX = 0x58,
+    KeyY = 0x59,
+    KeyZ = 0x5a,
     F1 = 0x70,
-    F2,
-    F3,
-    F4,
-    F5,
-    F6,
-    F7,
-    F8,
-    F9,
-    F10,
-    F11,
-    F12,
-    F13,
-    F14,
-    F15,
-    F16,
-    F17,
-    F18,
-    F19,
-    F20,
-    F21,
-    F22,
-    F23,
-    F24,
+    F2 = 0x71,
+    F3 = 0x72,
+    F4 = 0x73,
+    F5 = 0x74,
+    F6 = 0x75,
+    F7 = 0x76,
+    F8 = 0x77,
+    F9 = 0x78,
+    F10 = 0x79,
+    F11 = 0x7a,
+    F12 = 0x7b,
+    F13 = 0x7c,
+    F14 = 0x7d,
+    F15 = 0x7e,
+    F16 = 0x7f,
+    F17 = 0x80,
+    F18 = 0x81,
+    F19 = 0x82,
+    F20 = 0x83,
+    F21 = 0x84,
+    F22 = 0x85,
+    F23 = 0x86,
+    F24 = 0x87,
     NumLock = 0x90,
-    ScrollLock,
+    ScrollLock = 0x91,
+}
+
+impl FromPrimitive for HotkeyKey {
+    fn from_i64(n: i64) -> Option<Self> {
+        u8::try_from(n).ok().and_then(Self::from_u8)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        u8::try_from(n).ok().and_then(Self::from_u8)
+    }
+}
+
+impl BinRead for HotkeyKey {
+    type Args<'a> = ();
+
+    fn read_options<R: std::io::Read + std::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> binrw::BinResult<Self> {
+        u8::read_options(reader, endian, ()).map(Self::from)
+    }
+}
+
+impl BinWrite for HotkeyKey {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        _args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        u8::from(*self).write_options(writer, endian, ())
+    }
+}
+
+impl PartialEq for HotkeyKey {
+    /// compares the virtual key codes, so that [`HotkeyKey::Raw`] is equal to
+    /// the named variant of the same code
+    fn eq(&self, other: &Self) -> bool {
+        u8::from(*self) == u8::from(*other)
+    }
+}
+
+impl Eq for HotkeyKey {}
+
+impl Hash for HotkeyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u8::from(*self).hash(state);
+    }
+}
+
+impl fmt::Display for HotkeyKey {
+    /// formats the key like it is labeled on the keyboard, e.g. as `N` or
+    /// `F3`. Keys which are not modeled are formatted as their virtual key
+    /// code.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Self::from(u8::from(*self)) {
+            Self::NoKeyAssigned => write!(f, "None"),
+            Self::NumLock => write!(f, "Num Lock"),
+            Self::ScrollLock => write!(f, "Scroll Lock"),
+            Self::Raw(code) => write!(f, "{code:#04x}"),
+            key => {
+                let name = format!("{key:?}");
+                f.write_str(name.strip_prefix("Key").unwrap_or(&name))
+            }
+        }
+    }
 }
 
 bitflags! {
@@ -97,4 +97,18 @@ bitflags! {
     }
 }
 
-binread_flags!(LinkFlags, u32);
+binread_flags!(LinkFlags, u32, retain_unknown_bits);
+
+impl LinkFlags {
+    /// returns the bits which are set but not defined in the specification
+    pub fn unknown_bits(&self) -> u32 {
+        self.bits() & !Self::all().bits()
+    }
+
+    /// returns `true` if any bits are set which are not defined in the
+    /// specification. This may indicate a tampered shortcut or a newer
+    /// version of the file format.
+    pub fn has_unknown_bits(&self) -> bool {
+        self.unknown_bits() != 0
+    }
+}
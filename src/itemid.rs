@@ -2,10 +2,14 @@ use core::fmt;
 
 use binrw::BinRead;
 use getset::Getters;
+#[cfg(feature = "serde")]
 use serde::Serialize;
 
+use crate::DecodedSegment;
+
 /// The stored IDList structure specifies the format of a persisted item ID list.
 #[derive(Clone, BinRead, Default, Getters)]
+#[cfg_attr(feature = "binwrite", derive(binrw::BinWrite))]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[getset(get = "pub")]
 pub struct ItemID {
@@ -20,6 +24,23 @@ pub struct ItemID {
     data: Vec<u8>,
 }
 
+impl ItemID {
+    /// decodes the content of this item, as far as possible
+    pub fn decode(&self) -> DecodedSegment {
+        DecodedSegment::from(self)
+    }
+}
+
+/// A decoder for items which are not supported by [`ItemID::decode`], such
+/// as items created by a custom shell data source. Decoders can be passed to
+/// [`IdList::to_path_with`](crate::IdList::to_path_with), where they take
+/// precedence over the built-in decoding.
+pub trait ItemIdDecoder {
+    /// decodes `item`, or returns `None` if this decoder does not know the
+    /// item
+    fn decode(&self, item: &ItemID) -> Option<DecodedSegment>;
+}
+
 impl fmt::Debug for ItemID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ItemID (raw data size {})", self.size)
@@ -32,6 +32,13 @@
 //! ```
 //!
 //! > **IMPORTANT!**: Writing capability is currently in a very early stage and probably won't work!
+//!
+//! ## Features
+//!
+//! The `chrono` feature enables the accessors which return timestamps as
+//! [`chrono::DateTime<chrono::Utc>`], e.g. `ShellLink::write_time`. It
+//! does not remove the dependency on `chrono`, which is always required to
+//! convert timestamps, e.g. by [`FileTime::datetime`].
 
 use binrw::BinReaderExt;
 use getset::{Getters, MutGetters};
@@ -40,11 +47,15 @@ use log::{debug, error, info, trace, warn};
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+use std::collections::HashMap;
 use std::io::BufReader;
 #[cfg(feature = "binwrite")]
 use std::io::BufWriter;
-use std::path::Path;
-use std::{fs::File, io::Seek};
+use std::path::{Path, PathBuf};
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom},
+};
 
 mod header;
 pub use header::{
@@ -88,14 +99,45 @@ pub use strings::*;
 mod itemid;
 pub use itemid::*;
 
+mod decoded_segment;
+pub use decoded_segment::*;
+
 #[macro_use]
 mod binread_flags;
 
 mod error;
 pub use error::Error;
 
+mod open_options;
+pub use open_options::OpenOptions;
+
+mod builder;
+pub use builder::ShellLinkBuilder;
+
+mod target_source;
+pub use target_source::TargetSource;
+
+mod warning;
+pub use warning::Warning;
+
+mod trailer;
+pub use trailer::TrailerParser;
+
+mod policy;
+pub use policy::{PolicyViolation, ShortcutPolicy};
+
+mod encoded_payload;
+pub use encoded_payload::{DecodedPayload, PayloadEncoding};
+
+#[cfg(feature = "binwrite")]
+mod structural_diff;
+#[cfg(all(feature = "binwrite", feature = "unstable-save"))]
+pub use structural_diff::structural_diff;
+#[cfg(feature = "binwrite")]
+pub use structural_diff::{ChangeKind, StructuralChange, Structure};
+
 /// A shell link
-#[derive(Debug, Getters, MutGetters)]
+#[derive(Clone, Debug, Getters, MutGetters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[getset(get = "pub", get_mut = "pub")]
 pub struct ShellLink {
@@ -117,9 +159,32 @@ pub struct ShellLink {
     extra_data: extradata::ExtraData,
 
     /// encoding used for this link
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     #[getset(skip)]
     encoding: &'static encoding_rs::Encoding,
+
+    /// encoding used when writing the LinkInfo structure, if it differs from
+    /// the encoding of the StringData
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[getset(skip)]
+    link_info_encoding: Option<StringEncoding>,
+
+    /// the system default code page, which is used for the paths of the
+    /// LinkInfo in addition to their Unicode versions
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[getset(skip)]
+    code_page: &'static encoding_rs::Encoding,
+
+    /// problems which have been found while reading this link, and which are
+    /// reported by [`ShellLink::validate`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[getset(skip)]
+    read_warnings: Vec<Warning>,
+
+    /// the bytes which follow the ExtraData, see [`ShellLink::trailer`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[getset(skip)]
+    trailer: Vec<u8>,
 }
 
 impl Default for ShellLink {
@@ -140,6 +205,10 @@ impl Default for ShellLink {
             string_data: Default::default(),
             extra_data: Default::default(),
             encoding,
+            link_info_encoding: None,
+            code_page: encoding_rs::WINDOWS_1252,
+            read_warnings: Vec::new(),
+            trailer: Vec::new(),
         }
     }
 }
@@ -148,7 +217,6 @@ impl ShellLink {
     /// Create a new ShellLink pointing to a location, with otherwise default settings.
     pub fn new_simple<P: AsRef<Path>>(to: P) -> std::io::Result<Self> {
         use std::fs;
-        use std::path::PathBuf;
 
         let meta = fs::metadata(&to)?;
         let mut canonical = fs::canonicalize(&to)?.into_boxed_path();
@@ -178,8 +246,23 @@ impl ShellLink {
         Ok(sl)
     }
 
+    /// Creates a [`ShellLinkBuilder`], which can be used to create a new
+    /// ShellLink step by step
+    pub fn builder() -> ShellLinkBuilder {
+        ShellLinkBuilder::new()
+    }
+
     /// change the encoding for this link
     pub fn with_encoding(mut self, encoding: &StringEncoding) -> Self {
+        self.set_string_data_encoding(encoding);
+        self
+    }
+
+    /// Set the encoding of the [`StringData`], which is announced by the
+    /// [`LinkFlags::IS_UNICODE`] flag. Unless
+    /// [`ShellLink::set_link_info_encoding`] is used, this is also the
+    /// encoding of the [`LinkInfo`].
+    pub fn set_string_data_encoding(&mut self, encoding: &StringEncoding) {
         match encoding {
             StringEncoding::Unicode => {
                 self.header
@@ -192,106 +275,410 @@ impl ShellLink {
                     .link_flags_mut()
                     .set(LinkFlags::IS_UNICODE, false);
                 self.encoding = cp;
+                self.code_page = cp;
             }
         }
-        self
+    }
+
+    /// Changes the encoding of the [`StringData`] to `encoding`, like
+    /// [`ShellLink::set_string_data_encoding`], but checks that all strings
+    /// can be represented in the new encoding first. This includes the paths
+    /// of the [`LinkInfo`], unless its encoding has been set using
+    /// [`ShellLink::set_link_info_encoding`]. If a string cannot be converted
+    /// without loss, an [`Error::UnrepresentableString`] is returned and this
+    /// link is left unchanged.
+    pub fn reencode(&mut self, encoding: &StringEncoding) -> Result<(), Error> {
+        if let StringEncoding::CodePage(cp) = encoding {
+            let string_data = &self.string_data;
+            let link_info = self.link_info.as_ref().filter(|_| {
+                self.link_info_encoding.is_none()
+                    && self.header.link_flags().contains(LinkFlags::HAS_LINK_INFO)
+            });
+            let unrepresentable = [
+                string_data.name_string(),
+                string_data.relative_path(),
+                string_data.working_dir(),
+                string_data.command_line_arguments(),
+                string_data.icon_location(),
+            ]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(
+                link_info
+                    .map(LinkInfo::code_page_strings)
+                    .unwrap_or_default(),
+            )
+            .find(|s| cp.encode(s).2);
+            if let Some(string) = unrepresentable {
+                return Err(Error::UnrepresentableString {
+                    string: string.to_string(),
+                    encoding: cp.name(),
+                });
+            }
+        }
+        self.set_string_data_encoding(encoding);
+        Ok(())
+    }
+
+    /// Set the encoding which is used when writing the [`LinkInfo`],
+    /// independent of the encoding of the [`StringData`]. If it is
+    /// [`StringEncoding::Unicode`], the LinkInfo contains Unicode paths in
+    /// addition to the paths in the system default code page; otherwise, it
+    /// contains paths in the given code page only.
+    pub fn set_link_info_encoding(&mut self, encoding: &StringEncoding) {
+        self.link_info_encoding = Some(*encoding);
+    }
+
+    /// returns the encoding which is used when writing the [`LinkInfo`]. See
+    /// [`ShellLink::set_link_info_encoding`]
+    pub fn link_info_encoding(&self) -> StringEncoding {
+        self.link_info_encoding
+            .unwrap_or_else(|| StringEncoding::from(*self.header.link_flags(), self.encoding))
+    }
+
+    /// returns the name of the encoding which is used for the strings of this
+    /// link, e.g. `UTF-16LE` for Unicode links or `windows-1252`. See
+    /// [`encoding_rs::Encoding::name`]
+    pub fn encoding_name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// returns the bytes which follow the ExtraData, like a custom trailer
+    /// appended by some tool. Zero bytes directly following the
+    /// TerminalBlock are not part of the trailer, unless they are followed by
+    /// other data. The trailer is written
+    /// back unchanged when the link is saved. It is only read by
+    /// [`ShellLink::open`], [`ShellLink::from_bytes`] and if
+    /// [`OpenOptions::with_read_trailer`] is set.
+    pub fn trailer(&self) -> &[u8] {
+        &self.trailer
+    }
+
+    /// Set the bytes which follow the ExtraData, see [`ShellLink::trailer`]
+    pub fn set_trailer(&mut self, trailer: Vec<u8>) {
+        self.trailer = trailer;
+    }
+
+    /// interprets the trailer (see [`ShellLink::trailer`]) using `parser`.
+    /// If there is no trailer, or if `parser` doesn't know it, the result is
+    /// `None`.
+    pub fn with_trailer_parser<P: TrailerParser>(&self, parser: &P) -> Option<P::Output> {
+        if self.trailer.is_empty() {
+            return None;
+        }
+        parser.parse(&self.trailer)
     }
 
     /// Save a shell link.
     ///
-    /// Note that this doesn't save any [`ExtraData`](struct.ExtraData.html) entries.
+    /// [`ExtraData`](struct.ExtraData.html) blocks which have been read and
+    /// not been modified since are saved byte-exact; all other blocks are
+    /// serialized from their fields.
     #[cfg(feature = "binwrite")]
     #[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
     pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
-        use binrw::BinWrite;
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Serializes a shell link into a buffer, like [`ShellLink::save`]
+    /// does. The result can be parsed again using [`ShellLink::from_bytes`].
+    #[cfg(feature = "binwrite")]
+    #[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        self.write(&mut cursor)?;
+        Ok(cursor.into_inner())
+    }
+
+    /// Writes a shell link to a writer which doesn't support seeking, e.g. a
+    /// pipe. The link is serialized into a buffer first, which is then
+    /// written to `writer` at once.
+    ///
+    /// [`ExtraData`](struct.ExtraData.html) blocks which have been read and
+    /// not been modified since are written byte-exact; all other blocks are
+    /// serialized from their fields.
+    #[cfg(feature = "binwrite")]
+    #[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+    pub fn write_to_non_seekable<W: std::io::Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes()?)?;
+        writer.flush()?;
+        Ok(())
+    }
 
+    /// Save a shell link, reusing the byte layout of `reference`, which is
+    /// usually the file this link has been read from. Only the parts of this
+    /// link which differ from `reference` are serialized anew:
+    ///
+    /// * the header extension of `reference` is kept if this link has none
+    /// * strings of the [`StringData`] which have not been changed are copied
+    ///   from `reference`, keeping e.g. their trailing nulls
+    /// * the [`LinkTargetIdList`], the [`LinkInfo`] and the
+    ///   [`ExtraData`](struct.ExtraData.html) are copied from `reference`,
+    ///   keeping their original layout, unless they are missing in
+    ///   `reference` or have been changed. The IDList and the LinkInfo are
+    ///   omitted if their flags are not set in this link.
+    /// * the trailer is copied together with the ExtraData, or written from
+    ///   this link if the ExtraData or the trailer have been changed
+    #[cfg(feature = "binwrite")]
+    #[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+    pub fn save_like<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        reference: &[u8],
+    ) -> Result<(), Error> {
         let mut w = BufWriter::new(File::create(path)?);
+        self.write_like(&mut w, reference)
+    }
+
+    /// Serializes this link after clearing all fields which change without
+    /// the link itself being changed: the creation, access and write times
+    /// of the target, and the machine id and object ids of the tracker data.
+    /// Links which differ only in those fields have the same canonical bytes.
+    ///
+    /// Note that the [`ExtraData`](struct.ExtraData.html) blocks are
+    /// serialized from their fields instead of being written byte-exact,
+    /// because clearing the tracker data discards the bytes the blocks have
+    /// been read from.
+    #[cfg(feature = "binwrite")]
+    #[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut link = self.clone();
+        link.header.set_creation_time(FileTime::default());
+        link.header.set_access_time(FileTime::default());
+        link.header.set_write_time(FileTime::default());
+        for block in link.extra_data.blocks_mut() {
+            if let extradata::ExtraDataBlock::TrackerProps(tracker) = block {
+                tracker.clear();
+            }
+        }
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        link.write(&mut cursor)?;
+        Ok(cursor.into_inner())
+    }
+
+    #[cfg(feature = "binwrite")]
+    fn write<W: std::io::Write + Seek>(&self, w: &mut W) -> Result<(), Error> {
+        use binrw::BinWrite;
 
         debug!("Writing header...");
         // Invoke binwrite
         self.header()
-            .write_le(&mut w)
+            .write_le(w)
             .map_err(|be| Error::while_writing("Header", be))?;
 
         let link_flags = *self.header().link_flags();
 
+        if link_flags.contains(LinkFlags::HAS_LINK_TARGET_ID_LIST) {
+            self.write_id_list(w)?;
+        }
+
+        if link_flags.contains(LinkFlags::HAS_LINK_INFO) {
+            self.write_link_info(w)?;
+        }
+
         debug!("Writing StringData...");
         self.string_data
-            .write_le_args(&mut w, (link_flags, self.encoding))
+            .write_le_args(w, (link_flags, self.encoding))
             .map_err(|be| Error::while_writing("StringData", be))?;
 
-        // if link_flags.contains(LinkFlags::HAS_LINK_TARGET_ID_LIST) {
-        //     if let None = self.linktarget_id_list {
-        //         error!("LinkTargetIDList not specified but expected!")
-        //     }
-        //     debug!("A LinkTargetIDList is marked as present. Writing.");
-        //     let mut data: Vec<u8> = self.linktarget_id_list.clone().unwrap().into();
-        //     w.write_all(&mut data)?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_LINK_INFO) {
-        //     if let None = self.link_info {
-        //         error!("LinkInfo not specified but expected!")
-        //     }
-        //     debug!("LinkInfo is marked as present. Writing.");
-        //     let mut data: Vec<u8> = self.link_info.clone().unwrap().into();
-        //     w.write_all(&mut data)?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_NAME) {
-        //     if self.name_string == None {
-        //         error!("Name not specified but expected!")
-        //     }
-        //     debug!("Name is marked as present. Writing.");
-        //     w.write_all(&stringdata::to_data(
-        //         self.name_string.as_ref().unwrap(),
-        //         link_flags,
-        //     ))?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_RELATIVE_PATH) {
-        //     if self.relative_path == None {
-        //         error!("Relative path not specified but expected!")
-        //     }
-        //     debug!("Relative path is marked as present. Writing.");
-        //     w.write_all(&stringdata::to_data(
-        //         self.relative_path.as_ref().unwrap(),
-        //         link_flags,
-        //     ))?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_WORKING_DIR) {
-        //     if self.working_dir == None {
-        //         error!("Working Directory not specified but expected!")
-        //     }
-        //     debug!("Working dir is marked as present. Writing.");
-        //     w.write_all(&stringdata::to_data(
-        //         self.working_dir.as_ref().unwrap(),
-        //         link_flags,
-        //     ))?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_ARGUMENTS) {
-        //     if self.icon_location == None {
-        //         error!("Arguments not specified but expected!")
-        //     }
-        //     debug!("Arguments are marked as present. Writing.");
-        //     w.write_all(&stringdata::to_data(
-        //         self.command_line_arguments.as_ref().unwrap(),
-        //         link_flags,
-        //     ))?;
-        // }
-
-        // if link_flags.contains(LinkFlags::HAS_ICON_LOCATION) {
-        //     if self.icon_location == None {
-        //         error!("Icon Location not specified but expected!")
-        //     }
-        //     debug!("Icon Location is marked as present. Writing.");
-        //     w.write_all(&stringdata::to_data(
-        //         self.icon_location.as_ref().unwrap(),
-        //         link_flags,
-        //     ))?;
-        // }
+        debug!("Writing ExtraData...");
+        self.extra_data
+            .write_le(w)
+            .map_err(|be| Error::while_writing("ExtraData", be))?;
+
+        if !self.trailer.is_empty() {
+            debug!("Writing trailer...");
+            w.write_all(&self.trailer)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "binwrite")]
+    fn write_id_list<W: std::io::Write + Seek>(&self, w: &mut W) -> Result<(), Error> {
+        use binrw::BinWrite;
+
+        debug!("Writing LinkTargetIDList...");
+        match self.linktarget_id_list.as_ref() {
+            Some(id_list) => id_list.write_le(w),
+            None => Err(binrw::Error::AssertFail {
+                pos: w.stream_position()?,
+                message: "HasLinkTargetIDList is set, but there is no LinkTargetIDList".to_string(),
+            }),
+        }
+        .map_err(|be| Error::while_writing("LinkTargetIdList", be))
+    }
+
+    #[cfg(feature = "binwrite")]
+    fn write_link_info<W: std::io::Write + Seek>(&self, w: &mut W) -> Result<(), Error> {
+        use binrw::BinWrite;
+
+        debug!("Writing LinkInfo...");
+        match self.link_info.as_ref() {
+            Some(link_info) => {
+                link_info.write_le_args(w, (self.link_info_encoding(), self.code_page))
+            }
+            None => Err(binrw::Error::AssertFail {
+                pos: w.stream_position()?,
+                message: "HasLinkInfo is set, but there is no LinkInfo".to_string(),
+            }),
+        }
+        .map_err(|be| Error::while_writing("LinkInfo", be))
+    }
+
+    #[cfg(feature = "binwrite")]
+    fn write_like<W: std::io::Write + Seek>(
+        &self,
+        w: &mut W,
+        reference: &[u8],
+    ) -> Result<(), Error> {
+        use binrw::{BinWrite, Endian};
+        use std::io::Cursor;
+
+        let reference_link = Self::read_with_options(
+            &mut Cursor::new(reference),
+            self.code_page,
+            OpenOptions::default().with_read_trailer(true),
+        )?;
+        // structures which are not present in `reference` or which have been
+        // changed are serialized from this link
+        let changes = structural_diff::structural_diff(&reference_link, self);
+        let changed = |structure: structural_diff::Structure| {
+            changes.iter().any(|change| {
+                change.structure() == structure
+                    && change.kind() != structural_diff::ChangeKind::Removed
+            })
+        };
+
+        let mut reader = Cursor::new(reference);
+        let reference_header: ShellLinkHeader = reader
+            .read_le()
+            .map_err(|be| Error::while_parsing("ShellLinkHeader", be, &mut reader))?;
+        let reference_flags = *reference_header.link_flags();
+        let link_flags = *self.header().link_flags();
+
+        debug!("Writing header...");
+        let mut header = self.header.clone();
+        if header.header_extension().is_none() && reference_header.header_extension().is_some() {
+            header.set_header_size(*reference_header.header_size());
+            header.set_header_extension(reference_header.header_extension().clone());
+        }
+        header
+            .write_le(w)
+            .map_err(|be| Error::while_writing("Header", be))?;
+
+        let mut reference_id_list = None;
+        if reference_flags.contains(LinkFlags::HAS_LINK_TARGET_ID_LIST) {
+            let id_list_size: u16 = reader
+                .read_le()
+                .map_err(|be| Error::while_parsing("LinkTargetIdList", be, &mut reader))?;
+            let id_list = take_bytes(&mut reader, id_list_size.into(), "LinkTargetIdList")?;
+            reference_id_list = Some((id_list_size, id_list));
+        }
+        if link_flags.contains(LinkFlags::HAS_LINK_TARGET_ID_LIST) {
+            match reference_id_list {
+                Some((id_list_size, id_list))
+                    if !changed(structural_diff::Structure::LinkTargetIdList) =>
+                {
+                    debug!("Copying LinkTargetIDList...");
+                    w.write_all(&id_list_size.to_le_bytes())?;
+                    w.write_all(id_list)?;
+                }
+                _ => self.write_id_list(w)?,
+            }
+        }
+
+        let mut reference_link_info = None;
+        if reference_flags.contains(LinkFlags::HAS_LINK_INFO) {
+            let link_info_size: u32 = reader
+                .read_le()
+                .map_err(|be| Error::while_parsing("LinkInfo", be, &mut reader))?;
+            let link_info = take_bytes(
+                &mut reader,
+                u64::from(link_info_size).saturating_sub(4),
+                "LinkInfo",
+            )?;
+            reference_link_info = Some((link_info_size, link_info));
+        }
+        if link_flags.contains(LinkFlags::HAS_LINK_INFO) {
+            match reference_link_info {
+                Some((link_info_size, link_info))
+                    if !changed(structural_diff::Structure::LinkInfo) =>
+                {
+                    debug!("Copying LinkInfo...");
+                    w.write_all(&link_info_size.to_le_bytes())?;
+                    w.write_all(link_info)?;
+                }
+                _ => self.write_link_info(w)?,
+            }
+        }
+
+        debug!("Writing StringData...");
+        // strings can only be compared if both links use the same encoding
+        let same_encoding = link_flags.contains(LinkFlags::IS_UNICODE)
+            == reference_flags.contains(LinkFlags::IS_UNICODE);
+        for (flag, value) in [
+            (LinkFlags::HAS_NAME, self.string_data.name_string()),
+            (
+                LinkFlags::HAS_RELATIVE_PATH,
+                self.string_data.relative_path(),
+            ),
+            (LinkFlags::HAS_WORKING_DIR, self.string_data.working_dir()),
+            (
+                LinkFlags::HAS_ARGUMENTS,
+                self.string_data.command_line_arguments(),
+            ),
+            (
+                LinkFlags::HAS_ICON_LOCATION,
+                self.string_data.icon_location(),
+            ),
+        ] {
+            let start = reader.position() as usize;
+            let reference_value = parse_sized_string(
+                &mut reader,
+                Endian::Little,
+                (reference_flags, flag, self.encoding, OpenOptions::default()),
+            )
+            .map_err(|be| Error::while_parsing("StringData", be, &mut reader))?;
+            let reference_bytes = &reference[start..reader.position() as usize];
+
+            if !link_flags.contains(flag) {
+                continue;
+            }
+            let unchanged = same_encoding
+                && match (value, reference_value) {
+                    (Some(value), Some(reference_value)) => {
+                        value.strip_suffix('\0').unwrap_or(value) == reference_value
+                    }
+                    _ => false,
+                };
+            if unchanged {
+                w.write_all(reference_bytes)?;
+            } else {
+                write_sized_string(value, w, Endian::Little, (link_flags, flag, self.encoding))
+                    .map_err(|be| Error::while_writing("StringData", be))?;
+            }
+        }
+
+        let extra_data_changed = changes.iter().any(|change| {
+            matches!(
+                change.structure(),
+                structural_diff::Structure::ExtraDataBlock(_)
+            )
+        });
+        if extra_data_changed || self.trailer != reference_link.trailer {
+            debug!("Writing ExtraData...");
+            self.extra_data
+                .write_le(w)
+                .map_err(|be| Error::while_writing("ExtraData", be))?;
+            w.write_all(&self.trailer)?;
+        } else {
+            debug!("Copying ExtraData...");
+            w.write_all(&reference[reader.position() as usize..])?;
+        }
 
         Ok(())
     }
@@ -324,16 +711,105 @@ impl ShellLink {
     pub fn open<P: AsRef<std::path::Path>>(
         path: P,
         encoding: crate::strings::Encoding,
+    ) -> Result<Self, Error> {
+        Self::open_with_options(
+            path,
+            encoding,
+            OpenOptions::default().with_read_trailer(true),
+        )
+    }
+
+    /// Open and parse a shell link, using the given [`OpenOptions`]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the `lnk` file to be analyzed
+    /// * `encoding` - character encoding to be used if the `lnk` file is not
+    ///   Unicode encoded
+    /// * `options` - options which control the parsing
+    pub fn open_with_options<P: AsRef<std::path::Path>>(
+        path: P,
+        encoding: crate::strings::Encoding,
+        options: OpenOptions,
     ) -> Result<Self, Error> {
         debug!("Opening {:?}", path.as_ref());
         let mut reader = BufReader::new(File::open(path)?);
         trace!("Reading file.");
+        Self::read_with_options(&mut reader, encoding, options)
+    }
+
+    /// Parse a shell link from `reader`, starting at its current position.
+    /// This allows parsing shell links which are embedded in other files,
+    /// e.g. the streams of a jump list (`.automaticDestinations-ms`), after
+    /// positioning the reader at the start of the shell link. Offsets
+    /// reported by [`Error::offset`] are positions in `reader`.
+    ///
+    /// Parsing stops after the ExtraData, so that data following the link is
+    /// left in `reader`. Use [`OpenOptions::with_read_trailer`] to read the
+    /// remaining bytes as the trailer of the link (see
+    /// [`ShellLink::trailer`]).
+    ///
+    /// See [`ShellLink::open`] for a description of `encoding`.
+    pub fn read<R: std::io::Read + Seek>(
+        reader: &mut R,
+        encoding: crate::strings::Encoding,
+    ) -> Result<Self, Error> {
+        Self::read_with_options(reader, encoding, OpenOptions::default())
+    }
+
+    /// Parse a shell link from `reader`, like [`ShellLink::read`], but
+    /// buffers the reader first. This should be used for unbuffered
+    /// readers, like a [`File`].
+    pub fn read_buffered<R: std::io::Read + Seek>(
+        reader: R,
+        encoding: crate::strings::Encoding,
+    ) -> Result<Self, Error> {
+        Self::read(&mut BufReader::new(reader), encoding)
+    }
+
+    /// Parse a shell link from `reader`, starting at its current position,
+    /// like [`ShellLink::read`], but takes ownership of the reader. This is
+    /// convenient for shell links which are held in memory, e.g. in a
+    /// [`Cursor`](std::io::Cursor) over the bytes of an archive member.
+    pub fn from_reader<R: std::io::Read + Seek>(
+        mut reader: R,
+        encoding: crate::strings::Encoding,
+    ) -> Result<Self, Error> {
+        Self::read(&mut reader, encoding)
+    }
 
-        let shell_link_header: ShellLinkHeader = reader
+    /// Parse a shell link from `data`, like [`ShellLink::open`]. The bytes
+    /// following the ExtraData are read as the trailer of the link. If
+    /// `data` is shorter than a [`ShellLinkHeader`] or doesn't start with the
+    /// LinkCLSID, [`Error::NotAShellLinkError`] is returned without parsing
+    /// any further. Like [`ShellLink::open`], a HeaderSize larger than
+    /// `0x4C` is accepted.
+    pub fn from_bytes(data: &[u8], encoding: crate::strings::Encoding) -> Result<Self, Error> {
+        if data.len() < 0x4c {
+            return Err(Error::NotAShellLinkError);
+        }
+        Self::read_with_options(
+            &mut std::io::Cursor::new(data),
+            encoding,
+            OpenOptions::default().with_read_trailer(true),
+        )
+    }
+
+    /// Parse a shell link from `reader`, starting at its current position,
+    /// using the given [`OpenOptions`]. See [`ShellLink::read`].
+    pub fn read_with_options<R: std::io::Read + Seek>(
+        mut reader: &mut R,
+        encoding: crate::strings::Encoding,
+        options: OpenOptions,
+    ) -> Result<Self, Error> {
+        let link_start = reader.stream_position()?;
+        check_header_start(&mut reader)?;
+        let mut shell_link_header: ShellLinkHeader = reader
             .read_le()
-            .map_err(|be| Error::while_parsing("ShellLinkHeader", be))?;
+            .map_err(|be| Error::while_parsing("ShellLinkHeader", be, &mut reader))?;
         debug!("Shell header: {:#?}", shell_link_header);
 
+        let mut read_warnings = Vec::new();
         let mut linktarget_id_list = None;
         let link_flags = *shell_link_header.link_flags();
         if link_flags.contains(LinkFlags::HAS_LINK_TARGET_ID_LIST) {
@@ -342,8 +818,8 @@ impl ShellLink {
                 reader.stream_position()?
             );
             let list: LinkTargetIdList = reader
-                .read_le()
-                .map_err(|be| Error::while_parsing("LinkTargetIdList", be))?;
+                .read_le_args((options.max_idlist_items(),))
+                .map_err(|be| Error::while_parsing("LinkTargetIdList", be, &mut reader))?;
             debug!("LinkTargetIDList: {:#?}", list);
             linktarget_id_list = Some(list);
         }
@@ -355,41 +831,142 @@ impl ShellLink {
                 "LinkInfo is marked as present. Parsing now at position 0x{:0x}",
                 reader.stream_position().unwrap()
             );
-            let info: LinkInfo = reader
-                .read_le_args((encoding,))
-                .map_err(|be| Error::while_parsing("LinkInfo", be))?;
-            debug!("{:#?}", info);
-            debug_assert_eq!(
-                reader.stream_position().unwrap(),
-                link_info_offset + u64::from(*(info.link_info_size()))
-            );
-            link_info = Some(info);
+            let link_info_size: u32 = reader
+                .read_le()
+                .map_err(|be| Error::while_parsing("LinkInfo", be, &mut reader))?;
+
+            // some creators write an empty LinkInfo, which consists of
+            // nothing but its size field. The flag is cleared, so that the
+            // link can be written back.
+            if link_info_size <= 4 {
+                warn!("LinkInfo is marked as present, but has a size of {link_info_size}");
+                shell_link_header.update_link_flags(LinkFlags::HAS_LINK_INFO, false);
+                read_warnings.push(Warning::EmptyLinkInfo {
+                    size: link_info_size,
+                    offset: link_info_offset,
+                });
+            } else {
+                // the offsets of the LinkInfo cannot be trusted if its size
+                // is implausible, e.g. if the bytes belong to another
+                // structure
+                let file_size = reader.seek(SeekFrom::End(0))?;
+                if u64::from(link_info_size) > file_size - link_info_offset {
+                    warn!("LinkInfo has a size of {link_info_size}, which exceeds the file");
+                    return Err(Error::InvalidLinkInfoSize {
+                        size: link_info_size,
+                        offset: Some(link_info_offset),
+                    });
+                }
+                if link_info_size < linkinfo::MIN_LINK_INFO_SIZE {
+                    return Err(Error::InvalidLinkInfoSize {
+                        size: link_info_size,
+                        offset: Some(link_info_offset),
+                    });
+                }
+                reader.seek(SeekFrom::Start(link_info_offset))?;
+                let result: binrw::BinResult<LinkInfo> = reader.read_le_args((encoding,));
+                let position = reader.stream_position()?;
+                let info = match result {
+                    Ok(info) if info.has_plausible_strings() => {
+                        debug!("LinkInfo offsets are relative to the structure");
+                        info
+                    }
+                    result if options.detect_file_relative_offsets() => {
+                        match LinkInfo::read_file_relative(
+                            &mut reader,
+                            link_info_offset,
+                            link_info_offset - link_start,
+                            link_info_size,
+                            encoding,
+                        ) {
+                            Ok(info) if info.has_plausible_strings() => {
+                                warn!("LinkInfo offsets are relative to the start of the file");
+                                info
+                            }
+                            _ => {
+                                debug!("LinkInfo offsets are relative to the structure");
+                                reader.seek(SeekFrom::Start(position))?;
+                                result.map_err(|be| {
+                                    Error::while_parsing("LinkInfo", be, &mut reader)
+                                })?
+                            }
+                        }
+                    }
+                    result => {
+                        result.map_err(|be| Error::while_parsing("LinkInfo", be, &mut reader))?
+                    }
+                };
+                debug!("{:#?}", info);
+                debug_assert_eq!(
+                    reader.stream_position().unwrap(),
+                    link_info_offset + u64::from(*(info.link_info_size()))
+                );
+                link_info = Some(info);
+            }
         }
 
-        debug!(
-            "reading StringData at 0x{:08x}",
-            reader.stream_position().unwrap()
-        );
-        let string_data: StringData = reader
-            .read_le_args((link_flags, encoding))
-            .map_err(|be| Error::while_parsing("StringData", be))?;
+        let string_data_offset = reader.stream_position()?;
+        debug!("reading StringData at 0x{string_data_offset:08x}");
+        let mut string_data: StringData = reader
+            .read_le_args((link_flags, encoding, options))
+            .map_err(|be| Error::while_parsing("StringData", be, &mut reader))?;
         debug!("{:#?}", string_data);
 
+        let string_data_end = reader.stream_position()?;
+        if !extradata::peek_extra_data_start(&mut reader)? {
+            warn!("StringData ends at 0x{string_data_end:08x}, where no ExtraData block starts");
+            read_warnings.push(Warning::StringDataSizeMismatch {
+                offset: string_data_end,
+            });
+        }
+
         debug!(
             "reading ExtraData at 0x{:08x}",
             reader.stream_position().unwrap()
         );
         let extra_data: ExtraData = reader
             .read_le_args((encoding,))
-            .map_err(|be| Error::while_parsing("ExtraData", be))?;
+            .map_err(|be| Error::while_parsing("ExtraData", be, &mut reader))?;
 
+        let mut trailer = Vec::new();
+        if options.read_trailer() {
+            reader.read_to_end(&mut trailer)?;
+            if !trailer.is_empty() {
+                debug!("read {} bytes following the ExtraData", trailer.len());
+            }
+        }
+
+        let mut link_info_encoding = None;
+        let console_encoding = extra_data
+            .console_code_page()
+            .and_then(strings::encoding_for_code_page)
+            .filter(|console_encoding| {
+                options.use_console_code_page()
+                    && !link_flags.contains(LinkFlags::IS_UNICODE)
+                    && *console_encoding != encoding
+            });
+        if let Some(console_encoding) = console_encoding {
+            debug!(
+                "reading StringData again using the console code page {}",
+                console_encoding.name()
+            );
+            let end_of_link = reader.stream_position()?;
+            reader.seek(SeekFrom::Start(string_data_offset))?;
+            string_data = reader
+                .read_le_args((link_flags, console_encoding, options))
+                .map_err(|be| Error::while_parsing("StringData", be, &mut reader))?;
+            reader.seek(SeekFrom::Start(end_of_link))?;
+            link_info_encoding = Some(StringEncoding::CodePage(encoding));
+        }
+
+        let code_page = encoding;
         let encoding = if shell_link_header
             .link_flags()
             .contains(LinkFlags::IS_UNICODE)
         {
             encoding_rs::UTF_16LE
         } else {
-            encoding
+            console_encoding.unwrap_or(encoding)
         };
 
         Ok(Self {
@@ -399,6 +976,10 @@ impl ShellLink {
             string_data,
             extra_data,
             encoding,
+            link_info_encoding,
+            code_page,
+            read_warnings,
+            trailer,
         })
     }
 
@@ -406,9 +987,15 @@ impl ShellLink {
     /// is constructed completely from the LINK_INFO structure. So,
     /// if the lnk file does not contain such a structure, the result
     /// of this method will be `None`
+    ///
+    /// Network targets use the mapped device (e.g. `Z:`) if the
+    /// CommonNetworkRelativeLink has a valid device name, and the UNC share
+    /// otherwise, see [`CommonNetworkRelativeLink::name`].
+    ///
+    /// [`CommonNetworkRelativeLink::name`]: linkinfo::CommonNetworkRelativeLink::name
     pub fn link_target(&self) -> Option<String> {
         if let Some(info) = self.link_info().as_ref() {
-            let mut base_path = if info
+            let base_path = if info
                 .link_info_flags()
                 .has_common_network_relative_link_and_path_suffix()
             {
@@ -431,21 +1018,935 @@ impl ShellLink {
                 .map(|s| &s[..])
                 .unwrap_or(info.common_path_suffix());
 
-            // join base_path and common_path;
-            // make sure they're divided by exactly one '\' character.
-            // if common_path is empty, there's nothing to join.
-            if !common_path.is_empty() {
-                if !base_path.ends_with('\\') {
-                    base_path.push('\\');
+            Some(join_path(base_path, common_path))
+        } else {
+            None
+        }
+    }
+
+    /// returns the full path of a network link target, using the drive
+    /// letter (e.g. `Z:`) which was mapped to the network share when the
+    /// link was created instead of the UNC share. If the link does not
+    /// contain a DeviceName in its CommonNetworkRelativeLink structure, the
+    /// result of this method will be `None`
+    pub fn mapped_drive_target(&self) -> Option<String> {
+        let info = self.link_info().as_ref()?;
+        let device_name = info
+            .common_network_relative_link()
+            .as_ref()?
+            .device_name()?;
+        let common_path = info
+            .common_path_suffix_unicode()
+            .as_ref()
+            .map(|s| &s[..])
+            .unwrap_or(info.common_path_suffix());
+        Some(join_path(device_name.to_string(), common_path))
+    }
+
+    /// returns `true` if the link target is served by the WebDAV redirector,
+    /// which makes it possible to reach hosts on the internet through UNC
+    /// paths. This is the case if
+    ///
+    /// * the CommonNetworkRelativeLink of the [`LinkInfo`] specifies the
+    ///   network provider [`NetworkProviderType::Dav`](linkinfo::NetworkProviderType::Dav), or
+    /// * the target (see [`ShellLink::resolve_target`]) is a UNC path whose
+    ///   host uses the WebDAV syntax `\\host@SSL\...` or `\\host@port\...`,
+    ///   or whose share is `DavWWWRoot`.
+    pub fn is_webdav_target(&self) -> bool {
+        let dav_provider = self
+            .link_info
+            .as_ref()
+            .and_then(|info| info.common_network_relative_link().as_ref())
+            .and_then(|link| link.network_provider_type())
+            .is_some_and(|provider| matches!(provider, linkinfo::NetworkProviderType::Dav));
+        dav_provider
+            || self
+                .resolve_target()
+                .is_some_and(|target| is_webdav_path(&target))
+    }
+
+    /// returns the IDList which describes the link target. This is the
+    /// [`LinkTargetIdList`] if present, or else the IDList of the
+    /// [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock)
+    pub fn effective_idlist(&self) -> Option<&IdList> {
+        self.idlists().next()
+    }
+
+    /// returns the IDList which Windows Vista and later use for the link
+    /// target. This is the IDList of the
+    /// [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock)
+    /// if present, or else the [`LinkTargetIdList`]. See
+    /// [`ShellLink::effective_idlist`] for the opposite order.
+    pub fn preferred_id_list(&self) -> Option<&IdList> {
+        self.vista_idlists()
+            .next()
+            .or_else(|| self.linktarget_id_list.as_ref().map(|list| list.as_ref()))
+    }
+
+    /// returns `true` if this is an advertised shortcut, i.e. if it contains
+    /// a [`DarwinDataBlock`](extradata::darwin_data::DarwinDataBlock). Such
+    /// shortcuts are launched through the Windows Installer, which uses the
+    /// descriptor of the block instead of the link target.
+    pub fn is_advertised_shortcut(&self) -> bool {
+        self.extra_data.darwin().is_some()
+    }
+
+    /// splits the segments of the IDList (see [`ShellLink::effective_idlist`])
+    /// at the boundary which is stored in the
+    /// [`SpecialFolderDataBlock`](extradata::special_folder_data::SpecialFolderDataBlock).
+    /// Returns the segments which make up the special folder, and the
+    /// segments which are relative to it. If there is no such block, or its
+    /// offset does not point to the start of an ItemID, the result of this
+    /// method will be `None`
+    pub fn special_folder_split(&self) -> Option<(Vec<DecodedSegment>, Vec<DecodedSegment>)> {
+        let special_folder = self.extra_data.special_folder()?;
+        let id_list = self.effective_idlist()?;
+        let index = special_folder.item_index(id_list)?;
+
+        let (folder, relative) = id_list.item_id_list().split_at(index);
+        Some((
+            folder.iter().map(ItemID::decode).collect(),
+            relative.iter().map(ItemID::decode).collect(),
+        ))
+    }
+
+    /// returns the full path of the link target, using the first of the
+    /// following sources which yields a path:
+    ///
+    /// 1. the [`LinkInfo`] structure (see [`ShellLink::link_target`])
+    /// 2. the relative path, appended to the working directory. Both `\` and
+    ///    `/` are accepted as separators in these paths.
+    /// 3. the [`LinkTargetIdList`], and then the IDList of the
+    ///    [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock)
+    ///    (see [`IdList::to_path`])
+    /// 4. the shell namespace path of the IDList (see
+    ///    [`ShellLink::effective_idlist`] and [`IdList::to_shell_path`])
+    /// 5. the path of the
+    ///    [`EnvironmentVariableDataBlock`](extradata::environment_variable_data::EnvironmentVariableDataBlock),
+    ///    whose environment variables are not expanded (see
+    ///    [`ShellLink::environment_target`])
+    ///
+    /// So, the result is only `None` if the link contains neither an IDList
+    /// nor an EnvironmentVariableDataBlock.
+    pub fn resolve_target(&self) -> Option<String> {
+        self.resolution_trace()
+            .into_iter()
+            .find_map(|(_, target)| target)
+    }
+
+    /// returns every source which is considered by
+    /// [`ShellLink::resolve_target`], in the order in which they are tried,
+    /// together with the path which each of them yields. This can be used to
+    /// find out why a certain path has been chosen as target.
+    pub fn resolution_trace(&self) -> Vec<(TargetSource, Option<String>)> {
+        vec![
+            (TargetSource::LinkInfo, self.link_target()),
+            (TargetSource::RelativePath, self.relative_target()),
+            (
+                TargetSource::LinkTargetIdList,
+                self.linktarget_id_list
+                    .as_ref()
+                    .and_then(LinkTargetIdList::to_path),
+            ),
+            (
+                TargetSource::VistaAndAboveIdList,
+                self.vista_idlists().find_map(IdList::to_path),
+            ),
+            (
+                TargetSource::ShellNamespace,
+                self.effective_idlist().map(IdList::to_shell_path),
+            ),
+            (
+                TargetSource::EnvironmentVariables,
+                self.environment_target().map(str::to_string),
+            ),
+        ]
+    }
+
+    /// returns the path of the link target which is assembled from the IDList
+    /// using the short (8.3) names of its files and directories. Like in
+    /// [`ShellLink::resolve_target`], the [`LinkTargetIdList`] is tried first,
+    /// and then the IDList of the
+    /// [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock).
+    /// See [`IdList::to_short_path`]
+    pub fn target_short_path(&self) -> Option<String> {
+        self.idlists().find_map(IdList::to_short_path)
+    }
+
+    /// returns the path of the link target which is assembled from the IDList
+    /// using the long names of its files and directories. Where an entry has
+    /// no long name, its short name is used. See [`IdList::to_path`]
+    pub fn target_long_path(&self) -> Option<String> {
+        self.idlists().find_map(IdList::to_path)
+    }
+
+    /// returns the URL of the link target, if it is a web link rather than a
+    /// file. The URL is taken from the IDList if it refers to an URL
+    /// namespace (see [`IdList::to_url`]), or from the target returned by
+    /// [`ShellLink::resolve_target`] if that starts with an URL scheme like
+    /// `http://`. Characters which may not appear in an URL are
+    /// percent-encoded. Returns `None` for all other targets.
+    pub fn target_url(&self) -> Option<String> {
+        self.idlists().find_map(IdList::to_url).or_else(|| {
+            self.resolve_target()
+                .filter(|target| decoded_segment::is_url(target))
+                .map(|target| decoded_segment::percent_encode_url(&target))
+        })
+    }
+
+    /// returns the [`LinkTargetIdList`] and the IDList of the
+    /// [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock),
+    /// as far as they are present
+    fn idlists(&self) -> impl Iterator<Item = &IdList> {
+        self.linktarget_id_list
+            .as_ref()
+            .map(|list| list.as_ref())
+            .into_iter()
+            .chain(self.vista_idlists())
+    }
+
+    /// returns the IDLists of the
+    /// [`VistaAndAboveIdListDataBlock`](extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock)s
+    fn vista_idlists(&self) -> impl Iterator<Item = &IdList> {
+        self.extra_data
+            .blocks()
+            .iter()
+            .filter_map(|block| match block {
+                extradata::ExtraDataBlock::VistaAndAboveIdListProps(block) => Some(block.id_list()),
+                _ => None,
+            })
+    }
+
+    /// returns the relative path, appended to the working directory. Both
+    /// `\` and `/` are accepted as separators, but the result always uses
+    /// `\`.
+    fn relative_target(&self) -> Option<String> {
+        let working_dir = self.string_data.working_dir().as_ref()?;
+        let relative_path = self.string_data.relative_path().as_ref()?;
+        Some(normalize_path(working_dir, relative_path))
+    }
+
+    /// returns the working directory, with environment variables expanded
+    /// and `.` and `..` components resolved. The result always uses `\` as
+    /// separator. A relative working directory stays relative.
+    ///
+    /// The values of the environment variables are not taken from the host,
+    /// but derived from the link itself: the target path of the
+    /// [`EnvironmentVariableDataBlock`](extradata::environment_variable_data::EnvironmentVariableDataBlock)
+    /// (e.g. `%APPDATA%\App\app.exe`) is matched against the target of the
+    /// [`LinkInfo`] or the IDList (e.g.
+    /// `C:\Users\x\AppData\Roaming\App\app.exe`), which yields the value of
+    /// the variable it starts with. Variables whose values cannot be derived
+    /// are kept as they are.
+    pub fn resolved_working_dir(&self) -> Option<String> {
+        let working_dir = self.string_data.working_dir().as_ref()?;
+        let mut expanded = working_dir.clone();
+        for (name, value) in self.environment_values() {
+            expanded = replace_ignore_ascii_case(&expanded, &format!("%{name}%"), &value);
+        }
+        Some(normalize_path(&expanded, ""))
+    }
+
+    /// returns the path of the link target which is stored in the
+    /// [`EnvironmentVariableDataBlock`](extradata::environment_variable_data::EnvironmentVariableDataBlock),
+    /// e.g. `%SystemRoot%\notepad.exe`. See
+    /// [`EnvironmentVariableDataBlock::target`](extradata::environment_variable_data::EnvironmentVariableDataBlock::target)
+    /// for which of its paths is used.
+    pub fn environment_target(&self) -> Option<&str> {
+        self.extra_data
+            .environment_variables()
+            .map(|block| block.target())
+            .filter(|target| !target.is_empty())
+    }
+
+    /// derives the values of environment variables by matching the target
+    /// path of the EnvironmentVariableDataBlock, which starts with a
+    /// variable, against the resolved target path. See
+    /// [`ShellLink::resolved_working_dir`]
+    fn environment_values(&self) -> Vec<(String, String)> {
+        let Some(target) = self.link_target().or_else(|| self.target_long_path()) else {
+            return Vec::new();
+        };
+        let Some(block) = self.extra_data.environment_variables() else {
+            return Vec::new();
+        };
+
+        let mut values = Vec::new();
+        for env_target in [block.target_unicode().as_deref(), Some(block.target_ansi())]
+            .into_iter()
+            .flatten()
+        {
+            let Some((name, rest)) = env_target.strip_prefix('%').and_then(|s| s.split_once('%'))
+            else {
+                continue;
+            };
+            let Some(split) = target.len().checked_sub(rest.len()) else {
+                continue;
+            };
+            if target.is_char_boundary(split) && target[split..].eq_ignore_ascii_case(rest) {
+                values.push((name.to_string(), target[..split].to_string()));
+            }
+        }
+        values
+    }
+
+    /// returns the location of the icon of this link. The Unicode path of the
+    /// [`IconEnvironmentDataBlock`](extradata::icon_environment_data::IconEnvironmentDataBlock)
+    /// is preferred, followed by its ANSI path and the icon location of the
+    /// [`StringData`]. The paths of the IconEnvironmentDataBlock usually
+    /// contain environment variables, which are not expanded.
+    pub fn effective_icon_location(&self) -> Option<&str> {
+        let icon_environment = self.extra_data.icon_environment();
+        icon_environment
+            .and_then(|block| block.target_unicode().as_deref())
+            .into_iter()
+            .chain(icon_environment.map(|block| block.target_ansi().as_str()))
+            .chain(self.string_data.icon_location().as_deref())
+            .find(|location| !location.is_empty())
+    }
+
+    /// returns the file name of the link target, i.e. the last component of
+    /// its path (e.g. `notepad.exe`). The path is taken from
+    /// [`ShellLink::resolve_target`], or else from the relative path. If no
+    /// target can be determined or the target path ends with a separator,
+    /// the result of this method will be `None`
+    pub fn target_filename(&self) -> Option<String> {
+        let target = self
+            .resolve_target()
+            .or_else(|| self.string_data.relative_path().clone())?;
+        match target.rsplit(['\\', '/']).next() {
+            Some("") | None => None,
+            Some(name) => Some(name.to_string()),
+        }
+    }
+
+    /// returns the drive letter (in upper case) of the link target, if
+    /// [`ShellLink::resolve_target`] yields a path of the form `X:\...`.
+    /// For UNC paths and other targets, the result of this method will be
+    /// `None`
+    pub fn target_drive(&self) -> Option<char> {
+        let target = self.resolve_target()?;
+        let mut chars = target.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(drive), Some(':'), Some('\\') | None) if drive.is_ascii_alphabetic() => {
+                Some(drive.to_ascii_uppercase())
+            }
+            _ => None,
+        }
+    }
+
+    /// returns the full path of the link target like
+    /// [`ShellLink::resolve_target`], but replaces a drive letter found in
+    /// `mappings` (e.g. `Z`) by the UNC share which it is mapped to (e.g.
+    /// `\\nas\share`). Drive letters are matched case-insensitively. This
+    /// can be used to analyze links with the drive mappings of the machine
+    /// on which they were created.
+    pub fn resolve_target_with_mappings(&self, mappings: &HashMap<char, String>) -> Option<String> {
+        let target = self.resolve_target()?;
+        let share = self.target_drive().and_then(|drive| {
+            mappings
+                .iter()
+                .find(|(letter, _)| letter.eq_ignore_ascii_case(&drive))
+                .map(|(_, share)| share.clone())
+        });
+        match share {
+            Some(share) => Some(join_path(share, target[2..].trim_start_matches('\\'))),
+            None => Some(target),
+        }
+    }
+
+    /// returns the full path of the link target like
+    /// [`ShellLink::resolve_target`], but relocates it into the known folder
+    /// which is stored in the
+    /// [`KnownFolderDataBlock`](extradata::known_folder_data::KnownFolderDataBlock),
+    /// as Windows does when a known folder has been moved. `known_folders`
+    /// maps the ids of known folders to their current locations. The path is
+    /// assembled from the location of the known folder and the items of the
+    /// IDList which are relative to it.
+    ///
+    /// If [`LinkFlags::DISABLE_KNOWN_FOLDER_TRACKING`] is set, the known
+    /// folder is ignored and the literal path of the link is used, like it is
+    /// if the known folder is not found in `known_folders`.
+    pub fn resolve_target_with_known_folders(
+        &self,
+        known_folders: &HashMap<Guid, String>,
+    ) -> Option<String> {
+        if self
+            .header
+            .link_flags()
+            .contains(LinkFlags::DISABLE_KNOWN_FOLDER_TRACKING)
+        {
+            return self.resolve_target();
+        }
+        self.known_folder_target(known_folders)
+            .or_else(|| self.resolve_target())
+    }
+
+    /// assembles the path of the link target from the location of the known
+    /// folder in `known_folders` and the file entries of the IDList which
+    /// are relative to it
+    fn known_folder_target(&self, known_folders: &HashMap<Guid, String>) -> Option<String> {
+        let known_folder = self.extra_data.known_folder()?;
+        let location = known_folders.get(known_folder.known_folder_id())?;
+        let id_list = self.effective_idlist()?;
+        let index = known_folder.item_index(id_list)?;
+
+        id_list.item_id_list()[index..]
+            .iter()
+            .try_fold(location.clone(), |path, item| match item.decode() {
+                DecodedSegment::FileEntry(entry) => Some(join_path(path, entry.name())),
+                _ => None,
+            })
+    }
+
+    /// checks whether this link and `other` launch the same command, i.e.
+    /// whether their targets (see [`ShellLink::resolve_target`]) are equal,
+    /// ignoring case, and their command line arguments are equal. Cosmetic
+    /// differences like names or icons are ignored. If the target of either
+    /// link cannot be determined, the result is `false`.
+    pub fn same_target_as(&self, other: &ShellLink) -> bool {
+        match (self.resolve_target(), other.resolve_target()) {
+            (Some(target), Some(other_target)) => {
+                target.to_lowercase() == other_target.to_lowercase()
+                    && self.string_data.command_line_arguments()
+                        == other.string_data.command_line_arguments()
+            }
+            _ => false,
+        }
+    }
+
+    /// checks whether the link target, as returned by
+    /// [`ShellLink::resolve_target`], lies within one of the directories in
+    /// `roots`, e.g. `C:\Program Files`. Paths are compared case-insensitively
+    /// and component-wise, so that `C:\Windows` does not contain
+    /// `C:\WindowsApps`. The `.` and `..` components of the target and the
+    /// roots are resolved first, so that `C:\Windows\..\Users` is not within
+    /// `C:\Windows`. If the link target cannot be determined, the result of
+    /// this method will be `None`
+    pub fn target_within_roots(&self, roots: &[&str]) -> Option<bool> {
+        fn normalize(path: &str) -> String {
+            normalize_path(path, "").to_lowercase()
+        }
+
+        let target = normalize(&self.resolve_target()?);
+        let target: Vec<_> = target.split('\\').collect();
+        Some(roots.iter().any(|root| {
+            let root = normalize(root);
+            let root: Vec<_> = root.split('\\').collect();
+            target.starts_with(&root)
+        }))
+    }
+
+    /// checks this link against the rules of `policy`, and returns the rules
+    /// which are violated. If the link follows all rules, the result is
+    /// empty.
+    pub fn check_policy(&self, policy: &ShortcutPolicy) -> Vec<PolicyViolation> {
+        policy.check(self)
+    }
+
+    /// collects every string of this link which looks like a path or an URL,
+    /// i.e. which is rooted at a drive (e.g. `C:\`), is in UNC form (e.g.
+    /// `\\server\share`) or starts with `http://` or `https://`. The strings
+    /// are taken from the resolved targets, the [`StringData`], the
+    /// environment variable blocks and the string values of the property
+    /// store. Strings which contain several words, like command line
+    /// arguments, are also searched for quoted and unquoted words which look
+    /// like a path. Every string is returned only once.
+    pub fn path_like_strings(&self) -> Vec<String> {
+        let mut candidates = vec![
+            self.link_target(),
+            self.mapped_drive_target(),
+            self.resolve_target(),
+        ];
+        let string_data = &self.string_data;
+        candidates.extend([
+            string_data.name_string().clone(),
+            string_data.relative_path().clone(),
+            string_data.working_dir().clone(),
+            string_data.command_line_arguments().clone(),
+            string_data.icon_location().clone(),
+        ]);
+        for block in self.extra_data.blocks() {
+            match block {
+                extradata::ExtraDataBlock::EnvironmentProps(block) => {
+                    candidates.push(Some(block.target_ansi().clone()));
+                    candidates.push(block.target_unicode().clone());
+                }
+                extradata::ExtraDataBlock::IconEnvironmentProps(block) => {
+                    candidates.push(Some(block.target_ansi().clone()));
+                    candidates.push(block.target_unicode().clone());
+                }
+                extradata::ExtraDataBlock::PropertyStoreProps(block) => candidates.extend(
+                    block
+                        .property_stores()
+                        .iter()
+                        .flat_map(|store| store.properties().iter())
+                        .map(|(_, value)| value.as_str().map(str::to_string)),
+                ),
+                _ => (),
+            }
+        }
+
+        let mut result: Vec<String> = Vec::new();
+        for candidate in candidates.iter().flatten() {
+            let candidate = candidate.trim();
+            let parts = if is_path_like(candidate) {
+                vec![candidate]
+            } else {
+                // quoted parts are kept as they are, unquoted parts are split
+                // into words
+                candidate
+                    .split('"')
+                    .enumerate()
+                    .flat_map(|(index, part)| {
+                        if index % 2 == 1 {
+                            vec![part]
+                        } else {
+                            part.split_whitespace().collect()
+                        }
+                    })
+                    .filter(|part| is_path_like(part))
+                    .collect()
+            };
+            for part in parts {
+                if !result.iter().any(|s| s == part) {
+                    result.push(part.to_string());
                 }
-                base_path.push_str(common_path);
             }
-            Some(base_path)
+        }
+        result
+    }
+
+    /// searches the command line arguments for encoded payloads, which are
+    /// often used by malicious shortcuts to hide PowerShell commands, and
+    /// decodes them. The techniques which are detected are listed in
+    /// [`PayloadEncoding`]; payloads which are not valid base64 or which do
+    /// not decode to text are skipped. `certutil -decode` is not detected,
+    /// because it reads its payload from a file instead of the command line.
+    pub fn decode_encoded_arguments(&self) -> Vec<DecodedPayload> {
+        self.string_data
+            .command_line_arguments()
+            .as_deref()
+            .map(encoded_payload::decode_payloads)
+            .unwrap_or_default()
+    }
+
+    /// returns the path of the link target on the local file system. The
+    /// path is taken from the [`LinkInfo`] structure if present, or else
+    /// constructed by appending the relative path to the working directory.
+    fn local_target_path(&self) -> Option<PathBuf> {
+        if let Some(target) = self.link_target() {
+            return Some(PathBuf::from(target));
+        }
+
+        let path = self.relative_target()?;
+        Some(PathBuf::from(
+            path.replace('\\', std::path::MAIN_SEPARATOR_STR),
+        ))
+    }
+
+    /// Re-reads the attributes of the link target and updates the
+    /// [`FileAttributeFlags`] in the header accordingly. Use this after
+    /// changing the target of a link, so that the header does not describe
+    /// the previous target anymore.
+    ///
+    /// If the link target cannot be determined, the header is left unchanged.
+    pub fn refresh_target_attributes(&mut self) -> std::io::Result<()> {
+        let path = match self.local_target_path() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let meta = std::fs::metadata(&path)?;
+
+        #[cfg(windows)]
+        let mut attributes = {
+            use std::os::windows::fs::MetadataExt;
+            FileAttributeFlags::from_bits_truncate(meta.file_attributes())
+        };
+
+        #[cfg(not(windows))]
+        let mut attributes = {
+            let mut attributes = FileAttributeFlags::empty();
+            attributes.set(FileAttributeFlags::FILE_ATTRIBUTE_DIRECTORY, meta.is_dir());
+            attributes.set(
+                FileAttributeFlags::FILE_ATTRIBUTE_READONLY,
+                meta.permissions().readonly(),
+            );
+            attributes.set(
+                FileAttributeFlags::FILE_ATTRIBUTE_HIDDEN,
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with('.'))
+                    .unwrap_or(false),
+            );
+            attributes
+        };
+
+        if attributes.is_empty() {
+            attributes = FileAttributeFlags::FILE_ATTRIBUTE_NORMAL;
         } else {
-            None
+            attributes.remove(FileAttributeFlags::FILE_ATTRIBUTE_NORMAL);
+        }
+        debug!("refreshed attributes of {path:?}: {attributes:?}");
+        self.header_mut().set_file_attributes(attributes);
+        Ok(())
+    }
+
+    /// Copies the [`ExtraData`] blocks of `other` into this link. If
+    /// `replace_existing` is set, blocks of this link which have the same
+    /// signature as a copied block are replaced; otherwise, the copied blocks
+    /// are appended. The [`LinkFlags`] which announce the presence of the
+    /// copied blocks are set accordingly.
+    pub fn merge_extra_data_from(&mut self, other: &ShellLink, replace_existing: bool) {
+        for block in other.extra_data().blocks() {
+            if let Some(flag) = block.link_flag() {
+                self.header_mut().update_link_flags(flag, true);
+            }
+            self.extra_data.add_block(block.clone(), replace_existing);
         }
     }
 
+    /// returns the best known last modification time of the link target.
+    /// This is the write time of the header, unless it is zero; in that case,
+    /// the modification time of the last item of the IDList (see
+    /// [`ShellLink::effective_idlist`] and [`FileEntry::modification_time`])
+    /// is used, if it is a file entry.
+    pub fn effective_write_time(&self) -> Option<FileTime> {
+        let write_time = self.header.write_time();
+        if !write_time.is_zero() {
+            return Some(write_time.clone());
+        }
+        match self.effective_idlist()?.segments().last()? {
+            DecodedSegment::FileEntry(entry) => entry.modification_time().map(FileTime::from),
+            _ => None,
+        }
+    }
+
+    /// returns a single line describing this link, which is meant for log
+    /// output, e.g. `name="Editor" target="C:\Editor.exe" created=2008-09-12T20:27:18`.
+    /// String values are quoted, with any contained quotes escaped by a
+    /// backslash; fields which are empty or zero are omitted.
+    pub fn summary_line(&self) -> String {
+        fn quoted(key: &str, value: Option<&str>) -> Option<String> {
+            value
+                .filter(|value| !value.is_empty())
+                .map(|value| format!("{key}=\"{}\"", value.replace('"', "\\\"")))
+        }
+        fn timestamp(key: &str, time: &FileTime) -> Option<String> {
+            (!time.is_zero())
+                .then(|| format!("{key}={}", time.datetime().format("%Y-%m-%dT%H:%M:%S")))
+        }
+
+        let target = self.resolve_target();
+        [
+            quoted("name", self.string_data.name_string().as_deref()),
+            quoted("target", target.as_deref()),
+            quoted("args", self.string_data.command_line_arguments().as_deref()),
+            quoted("workdir", self.string_data.working_dir().as_deref()),
+            timestamp("created", self.header.creation_time()),
+            timestamp("modified", self.header.write_time()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    /// the columns of [`ShellLink::to_csv_record`]
+    const CSV_HEADER: [&'static str; 7] = [
+        "name",
+        "target",
+        "arguments",
+        "working_dir",
+        "created",
+        "machine_id",
+        "drive_type",
+    ];
+
+    /// returns the names of the columns of [`ShellLink::to_csv_record`], in
+    /// the same order
+    pub fn csv_header() -> Vec<&'static str> {
+        Self::CSV_HEADER.to_vec()
+    }
+
+    /// returns the key fields of this link as a flat record, which is meant
+    /// for bulk analysis in spreadsheets. The columns are described by
+    /// [`ShellLink::csv_header`]: the name, the target (see
+    /// [`ShellLink::resolve_target`]), the command line arguments, the
+    /// working directory, the creation time (e.g. `2008-09-12T20:27:18`), the
+    /// machine id of the [`TrackerDataBlock`](extradata::tracker_data::TrackerDataBlock)
+    /// and the type of the drive the target is stored on (e.g. `Fixed disk`).
+    /// Fields which are missing or zero are empty.
+    ///
+    /// The values are returned as they are; commas, quotes and line breaks
+    /// are not escaped; this is left to the CSV writer of the caller.
+    pub fn to_csv_record(&self) -> Vec<String> {
+        let creation_time = self.header.creation_time();
+        let drive_type = self
+            .link_info
+            .as_ref()
+            .and_then(|info| info.volume_id().as_ref())
+            .map(|volume_id| volume_id.drive_type().to_string());
+        [
+            self.string_data.name_string().clone(),
+            self.resolve_target(),
+            self.string_data.command_line_arguments().clone(),
+            self.string_data.working_dir().clone(),
+            (!creation_time.is_zero()).then(|| {
+                creation_time
+                    .datetime()
+                    .format("%Y-%m-%dT%H:%M:%S")
+                    .to_string()
+            }),
+            self.extra_data
+                .tracker()
+                .map(|tracker| tracker.machine_id().clone()),
+            drive_type,
+        ]
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect()
+    }
+
+    /// the property set of `System.Thumbnail` and `System.ThumbnailStream`
+    const THUMBNAIL: uuid::Uuid = uuid::uuid!("F29F85E0-4FF9-1068-AB91-08002B27B3D9");
+
+    /// the property ids of `System.ThumbnailStream` and `System.Thumbnail`,
+    /// in the order in which they are searched
+    const THUMBNAIL_IDS: [u32; 2] = [27, 17];
+
+    /// returns the AppUserModelID of this link, which is used by Windows to
+    /// group the windows of an application on the taskbar. It is stored in
+    /// the property store as `System.AppUserModel.ID`.
+    pub fn app_user_model_id(&self) -> Option<String> {
+        self.extra_data.property_store()?.app_user_model_id()
+    }
+
+    /// returns the raw bytes of the thumbnail which is stored in the property
+    /// store as `System.ThumbnailStream` or `System.Thumbnail`, if it is a
+    /// blob or a stream. The format of the image is not checked.
+    pub fn thumbnail_data(&self) -> Option<&[u8]> {
+        use extradata::property_store_data::{PropVariant, PropertyKey};
+        let property_store = self.extra_data.property_store()?;
+        Self::THUMBNAIL_IDS.iter().find_map(|id| {
+            property_store
+                .get(&Guid::from(Self::THUMBNAIL), &PropertyKey::Integer(*id))
+                .and_then(PropVariant::as_bytes)
+        })
+    }
+
+    /// returns `true` if this link carries a thumbnail, see
+    /// [`ShellLink::thumbnail_data`]
+    pub fn has_thumbnail(&self) -> bool {
+        self.thumbnail_data().is_some()
+    }
+
+    /// Set the AppUserModelID of this link (see
+    /// [`ShellLink::app_user_model_id`]). Other properties of an existing
+    /// property store are kept.
+    pub fn set_app_user_model_id(&mut self, id: &str) {
+        use extradata::property_store_data::{
+            PropVariant, PropertyKey, PropertyStoreBuilder, APP_USER_MODEL, APP_USER_MODEL_ID,
+        };
+        let builder = self
+            .extra_data
+            .property_store()
+            .map(PropertyStoreBuilder::from)
+            .unwrap_or_default();
+        let block = builder
+            .property(
+                Guid::from(APP_USER_MODEL),
+                PropertyKey::Integer(APP_USER_MODEL_ID),
+                PropVariant::LpWStr(id.to_string()),
+            )
+            .build();
+        self.extra_data
+            .add_block(extradata::ExtraDataBlock::PropertyStoreProps(block), true);
+    }
+
+    /// checks this link for inconsistencies which did not prevent it from
+    /// being read, but which indicate that it has been corrupted or
+    /// manipulated. The following checks are made:
+    ///
+    /// * the CommonNetworkRelativeLinkSize must match the size of the fields
+    ///   of the [`CommonNetworkRelativeLink`](linkinfo::CommonNetworkRelativeLink)
+    ///   which are present
+    /// * the offset of the
+    ///   [`SpecialFolderDataBlock`](extradata::special_folder_data::SpecialFolderDataBlock)
+    ///   must point to an ItemID of the IDList, if there is one (see
+    ///   [`ShellLink::effective_idlist`])
+    /// * when the link has been read, the [`StringData`] must be followed by
+    ///   the ExtraData or by the end of the input. Otherwise, the lengths of
+    ///   its strings are inconsistent, and its strings or the ExtraData may
+    ///   have been read incorrectly.
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = self.read_warnings.clone();
+        if let Some(link) = self
+            .link_info
+            .as_ref()
+            .and_then(|info| info.common_network_relative_link().as_ref())
+        {
+            warnings.extend(link.size_warning());
+        }
+        if let (Some(special_folder), Some(id_list)) =
+            (self.extra_data.special_folder(), self.effective_idlist())
+        {
+            if special_folder.item_index(id_list).is_none() {
+                warnings.push(Warning::SpecialFolderOffsetMismatch {
+                    offset: *special_folder.offset(),
+                });
+            }
+        }
+        warnings
+    }
+
+    /// checks whether the strings of the [`StringData`] are plausible for the
+    /// encoding which is declared by the [`LinkFlags::IS_UNICODE`] flag. This
+    /// is a heuristic, which returns `false` if
+    ///
+    /// * the flag is set, but a string consists only of characters which are
+    ///   made of two printable ASCII bytes, i.e. single-byte text has been
+    ///   read as UTF-16. Text in some CJK scripts may match this pattern, too.
+    /// * the flag is not set, but a string contains null characters, i.e.
+    ///   UTF-16 text has been read as single-byte text.
+    pub fn string_encoding_consistent(&self) -> bool {
+        let is_paired_ascii = |c: char| {
+            u16::try_from(u32::from(c))
+                .is_ok_and(|unit| unit.to_le_bytes().iter().all(|b| (0x20..=0x7e).contains(b)))
+        };
+        let is_unicode = self.header.link_flags().contains(LinkFlags::IS_UNICODE);
+        let string_data = &self.string_data;
+        [
+            string_data.name_string(),
+            string_data.relative_path(),
+            string_data.working_dir(),
+            string_data.command_line_arguments(),
+            string_data.icon_location(),
+        ]
+        .into_iter()
+        .flatten()
+        .all(|s| {
+            if is_unicode {
+                s.is_empty() || !s.chars().all(is_paired_ascii)
+            } else {
+                !s.contains('\0')
+            }
+        })
+    }
+
+    /// returns `true` if activating this link asks for elevated privileges.
+    /// This is the case if
+    ///
+    /// * the [`LinkFlags::RUN_AS_USER`] flag is set, which is what the
+    ///   "Run as administrator" checkbox of the shortcut properties sets, or
+    /// * the [`ShimDataBlock`](extradata::shim_data::ShimDataBlock) applies
+    ///   one of the compatibility layers `RunAsAdmin` or `RunAsHighest`.
+    ///
+    /// Windows defines no property of the property store which requests
+    /// elevation, so the property store is not checked. An elevation
+    /// requested by the manifest of the target is not visible in the link.
+    pub fn requires_elevation(&self) -> bool {
+        const ELEVATING_LAYERS: [&str; 2] = ["RUNASADMIN", "RUNASHIGHEST"];
+        self.header.link_flags().contains(LinkFlags::RUN_AS_USER)
+            || self.extra_data.shim_layer().is_some_and(|layers| {
+                layers
+                    .split_whitespace()
+                    .any(|layer| ELEVATING_LAYERS.contains(&layer.to_uppercase().as_str()))
+            })
+    }
+
+    /// Returns a copy of this link without command line arguments, i.e. with
+    /// the arguments cleared and [`LinkFlags::HAS_ARGUMENTS`] unset. All
+    /// other fields are preserved.
+    pub fn without_arguments(&self) -> ShellLink {
+        let mut link = self.clone();
+        link.set_arguments(None);
+        link
+    }
+
+    /// returns the creation time of the link target, or `None` if it is not
+    /// set. See [`FileTime::utc_datetime`]
+    #[cfg(feature = "chrono")]
+    pub fn creation_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.header.creation_time().utc_datetime()
+    }
+
+    /// returns the access time of the link target, or `None` if it is not
+    /// set. See [`FileTime::utc_datetime`]
+    #[cfg(feature = "chrono")]
+    pub fn access_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.header.access_time().utc_datetime()
+    }
+
+    /// returns the write time of the link target, or `None` if it is not
+    /// set. See [`FileTime::utc_datetime`]
+    #[cfg(feature = "chrono")]
+    pub fn write_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.header.write_time().utc_datetime()
+    }
+
+    /// returns the expected window state of the application launched by
+    /// this link
+    pub fn show_command(&self) -> ShowCommand {
+        *self.header.show_command()
+    }
+
+    /// Set the expected window state of the application launched by this
+    /// link
+    pub fn set_show_command(&mut self, show_command: ShowCommand) {
+        self.header.set_show_command(show_command);
+    }
+
+    /// returns the expected window state of the application launched by
+    /// this link as a number, like the `WindowStyle` property of the
+    /// `WScript.Shell` shortcut object: 1 for a normal window, 3 for a
+    /// maximized window and 7 for a minimized window
+    pub fn window_style(&self) -> u32 {
+        self.show_command() as u32
+    }
+
+    /// Set the expected window state of the application launched by this
+    /// link as a number, see [`ShellLink::window_style`]. Other values than
+    /// 1, 3 and 7 are rejected with [`Error::InvalidWindowStyle`].
+    pub fn set_window_style(&mut self, style: u32) -> Result<(), Error> {
+        let show_command = match style {
+            1 => ShowCommand::ShowNormal,
+            3 => ShowCommand::ShowMaximized,
+            7 => ShowCommand::ShowMinNoActive,
+            style => return Err(Error::InvalidWindowStyle { style }),
+        };
+        self.set_show_command(show_command);
+        Ok(())
+    }
+
+    /// returns the hotkey of this link, or `None` if no hotkey is assigned.
+    /// Use the `Display` implementation of [`HotkeyFlags`] to show it, e.g.
+    /// as `Ctrl+Alt+N`.
+    pub fn hotkey(&self) -> Option<(HotkeyModifiers, HotkeyKey)> {
+        let hotkey = self.header.hotkey();
+        (*hotkey.key() != HotkeyKey::NoKeyAssigned).then_some((*hotkey.modifiers(), *hotkey.key()))
+    }
+
+    /// Set the shell link's hotkey, or remove it if `hotkey` is `None`
+    pub fn set_hotkey(&mut self, hotkey: Option<(HotkeyModifiers, HotkeyKey)>) {
+        let (modifiers, key) =
+            hotkey.unwrap_or((HotkeyModifiers::NO_MODIFIER, HotkeyKey::NoKeyAssigned));
+        self.header.set_hotkey(HotkeyFlags::new(key, modifiers));
+    }
+
+    /// returns the hotkey of this link packed into a WORD, as used by the
+    /// Win32 API: the low byte contains the virtual key code, the high byte
+    /// contains the modifiers
+    pub fn hotkey_word(&self) -> u16 {
+        (*self.header.hotkey()).into()
+    }
+
+    /// Set the shell link's hotkey from a WORD, as used by the Win32 API.
+    /// See [`HotkeyFlags`] for how unknown keys and modifiers are handled.
+    pub fn set_hotkey_word(&mut self, hotkey: u16) {
+        self.header.set_hotkey(hotkey.into());
+    }
+
     /// Set the shell link's name
     pub fn set_name(&mut self, name: Option<String>) {
         self.header_mut()
@@ -453,6 +1954,35 @@ impl ShellLink {
         self.string_data_mut().set_name_string(name);
     }
 
+    /// returns the shell link's description, which is shown as "Comment" in
+    /// the properties dialog of Windows. In MS-SHLLINK, this is the
+    /// NAME_STRING of the [`StringData`], so this is the same value as
+    /// [`StringData::name_string`].
+    #[doc(alias = "comment")]
+    pub fn description(&self) -> Option<&str> {
+        self.string_data.name_string().as_deref()
+    }
+
+    /// Set the shell link's description, which is shown as "Comment" in the
+    /// properties dialog of Windows. This is the same as
+    /// [`ShellLink::set_name`].
+    #[doc(alias = "set_comment")]
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.set_name(description);
+    }
+
+    /// returns the shell link's description (see [`ShellLink::description`])
+    /// for display, truncated to its first `max_chars` characters. If the
+    /// description has been truncated, "…" is appended. Links without a
+    /// description yield an empty string.
+    pub fn display_name_truncated(&self, max_chars: usize) -> String {
+        let name = self.description().unwrap_or_default();
+        match name.char_indices().nth(max_chars) {
+            Some((end, _)) => format!("{}…", &name[..end]),
+            None => name.to_string(),
+        }
+    }
+
     /// Set the shell link's relative path
     pub fn set_relative_path(&mut self, relative_path: Option<String>) {
         self.header_mut()
@@ -480,4 +2010,246 @@ impl ShellLink {
             .update_link_flags(LinkFlags::HAS_ICON_LOCATION, icon_location.is_some());
         self.string_data_mut().set_icon_location(icon_location);
     }
+
+    /// Set the shell link's icon location to `relative_icon` and its icon
+    /// index to `index`. A relative icon location, such as `.\\app.ico`, is
+    /// resolved by Windows against the directory which contains the shell
+    /// link, so the icon moves together with the shell link.
+    pub fn set_relative_icon(&mut self, relative_icon: &str, index: i32) {
+        self.set_icon_location(Some(relative_icon.to_string()));
+        self.header_mut().set_icon_index(index);
+    }
+}
+
+/// the LinkCLSID every [`ShellLinkHeader`] starts with
+const LINK_CLSID: uuid::Uuid = uuid::uuid!("00021401-0000-0000-C000-000000000046");
+
+/// checks the HeaderSize and LinkCLSID fields at the current position of
+/// `reader`, without advancing it. If there are not enough bytes for these
+/// fields, the check is left to the parser of the header, which reports the
+/// unexpected End-of-File.
+fn check_header_start<R: std::io::Read + Seek>(reader: &mut R) -> Result<(), Error> {
+    use std::io::Read;
+
+    let offset = reader.stream_position()?;
+    let mut bytes = Vec::with_capacity(20);
+    reader.by_ref().take(20).read_to_end(&mut bytes)?;
+    reader.seek(SeekFrom::Start(offset))?;
+    if bytes.len() < 20 {
+        return Ok(());
+    }
+
+    if bytes[4..20] != LINK_CLSID.to_bytes_le() {
+        return Err(Error::NotAShellLinkError);
+    }
+    let size = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+    if size < 0x4c {
+        return Err(Error::InvalidHeaderSize {
+            size,
+            offset: Some(offset),
+        });
+    }
+    Ok(())
+}
+
+/// Checks whether `bytes` start with a [`ShellLinkHeader`], by looking at
+/// the HeaderSize and LinkCLSID fields only. This is much faster than
+/// parsing the whole shell link, but does not guarantee that the shell link
+/// can be parsed. Only the first 20 bytes are inspected; if `bytes` is
+/// shorter, the result is `false`.
+pub fn is_shell_link(bytes: &[u8]) -> bool {
+    match (bytes.get(..4), bytes.get(4..20)) {
+        (Some(header_size), Some(link_clsid)) => {
+            header_size == 0x4cu32.to_le_bytes() && link_clsid == LINK_CLSID.to_bytes_le()
+        }
+        _ => false,
+    }
+}
+
+/// returns the next `len` bytes of `reader` and advances it accordingly.
+/// If there are not enough bytes left, an [`Error::UnexpectedEof`] is
+/// returned.
+#[cfg(feature = "binwrite")]
+fn take_bytes<'a>(
+    reader: &mut std::io::Cursor<&'a [u8]>,
+    len: u64,
+    context: &'static str,
+) -> Result<&'a [u8], Error> {
+    let bytes = *reader.get_ref();
+    let start = reader.position();
+    let end = start
+        .checked_add(len)
+        .filter(|end| *end <= bytes.len() as u64)
+        .ok_or(Error::UnexpectedEof {
+            context,
+            offset: Some(bytes.len() as u64),
+        })?;
+    reader.set_position(end);
+    Ok(&bytes[start as usize..end as usize])
+}
+
+/// Opens all shell links in `dir`, which usually is the recent items folder
+/// `%APPDATA%\Microsoft\Windows\Recent`. Only files with the extension `.lnk`
+/// are considered; subdirectories are not scanned. A file which cannot be
+/// parsed doesn't affect the other files, its error is returned together
+/// with its path instead. The result is sorted by path. If `dir` cannot be
+/// read, the result is empty.
+pub fn open_recent_items(
+    dir: &Path,
+    encoding: crate::strings::Encoding,
+) -> Vec<(PathBuf, Result<ShellLink, Error>)> {
+    link_files(dir)
+        .into_iter()
+        .map(|path| {
+            let link = ShellLink::open(&path, encoding);
+            (path, link)
+        })
+        .collect()
+}
+
+/// Changes the encoding of all shell links in `dir` to `to` (see
+/// [`ShellLink::reencode`]) and saves them in place. Links which are not
+/// Unicode are read using the code page `from`. Like in
+/// [`open_recent_items`], only files with the extension `.lnk` are
+/// considered. The result for every file is returned together with its
+/// path; a file which cannot be parsed, converted or serialized is left
+/// untouched. Every link is written to a temporary file in `dir`, which then
+/// replaces the original file. If `dir` cannot be read, the result is empty.
+#[cfg(feature = "binwrite")]
+#[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+pub fn reencode_dir(
+    dir: &Path,
+    from: crate::strings::Encoding,
+    to: StringEncoding,
+) -> Vec<(PathBuf, Result<(), Error>)> {
+    link_files(dir)
+        .into_iter()
+        .map(|path| {
+            let result = ShellLink::open(&path, from).and_then(|mut link| {
+                link.reencode(&to)?;
+                let bytes = link.to_bytes()?;
+
+                // the link is written to a temporary file first, so that it
+                // is not truncated if writing fails
+                let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+                temp_name.push(".tmp");
+                let temp_path = path.with_file_name(temp_name);
+                let result = std::fs::write(&temp_path, bytes)
+                    .and_then(|_| std::fs::rename(&temp_path, &path));
+                if result.is_err() {
+                    let _ = std::fs::remove_file(&temp_path);
+                }
+                Ok(result?)
+            });
+            (path, result)
+        })
+        .collect()
+}
+
+/// returns the paths of all files in `dir` with the extension `.lnk`, sorted
+/// by path. Subdirectories are not scanned.
+fn link_files(dir: &Path) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(why) => {
+            warn!("unable to read directory {dir:?}: {why}");
+            return Vec::new();
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .map(|ext| ext.eq_ignore_ascii_case("lnk"))
+                .unwrap_or(false)
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// checks whether `s` is rooted at a drive, is in UNC form, or is a HTTP(S)
+/// URL
+fn is_path_like(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let drive_rooted = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    let unc = s.len() > 2 && s.starts_with("\\\\");
+    let lowercase = s.to_ascii_lowercase();
+    drive_rooted || unc || lowercase.starts_with("http://") || lowercase.starts_with("https://")
+}
+
+/// checks whether `path` is a UNC path which is served by the WebDAV
+/// redirector, i.e. whose host is followed by `@SSL` or `@port`, or whose
+/// share is `DavWWWRoot`
+fn is_webdav_path(path: &str) -> bool {
+    let Some(unc) = path.strip_prefix("\\\\") else {
+        return false;
+    };
+    let mut components = unc.split(['\\', '/']);
+    let host = components.next().unwrap_or_default();
+    let share = components.next().unwrap_or_default();
+    let dav_host = host.split('@').skip(1).any(|suffix| {
+        suffix.eq_ignore_ascii_case("SSL")
+            || (!suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit()))
+    });
+    dav_host || share.eq_ignore_ascii_case("DavWWWRoot")
+}
+
+/// joins `base_path` and `common_path`, making sure they're divided by
+/// exactly one '\' character. If `common_path` is empty, there's nothing
+/// to join.
+fn join_path(mut base_path: String, common_path: &str) -> String {
+    if !common_path.is_empty() {
+        if !base_path.ends_with('\\') {
+            base_path.push('\\');
+        }
+        base_path.push_str(common_path);
+    }
+    base_path
+}
+
+/// appends `relative` to `base`, and resolves the `.` and `..` components of
+/// both. Both `\\` and `/` are accepted as separators, but the result always
+/// uses `\\`. A leading `\\\\` or a leading separator of `base` is kept.
+fn normalize_path(base: &str, relative: &str) -> String {
+    let mut components = Vec::new();
+    for component in base.split(['\\', '/']).chain(relative.split(['\\', '/'])) {
+        match component {
+            "" | "." => (),
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    let prefix = if base.starts_with("\\\\") {
+        "\\\\"
+    } else if base.starts_with(['\\', '/']) {
+        "\\"
+    } else {
+        ""
+    };
+    format!("{prefix}{}", components.join("\\"))
+}
+
+/// replaces all occurrences of `from` in `s` by `to`, ignoring the case of
+/// ASCII characters
+fn replace_ignore_ascii_case(s: &str, from: &str, to: &str) -> String {
+    let lowercase = s.to_ascii_lowercase();
+    let from = from.to_ascii_lowercase();
+    let mut result = String::with_capacity(s.len());
+    let mut last = 0;
+    for (start, _) in lowercase.match_indices(&from) {
+        result.push_str(&s[last..start]);
+        result.push_str(to);
+        last = start + from.len();
+    }
+    result.push_str(&s[last..]);
+    result
 }
@@ -1,34 +1,44 @@
 use core::panic;
+use std::{
+    fmt::Display,
+    io::{Cursor, Read, Seek, SeekFrom},
+};
 
-use binrw::BinRead;
+use binrw::{BinRead, BinReaderExt, BinResult};
 use bitflags::bitflags;
 use encoding_rs::Encoding;
+#[cfg(feature = "binwrite")]
+use encoding_rs::UTF_16LE;
 use getset::Getters;
 use num_derive::{FromPrimitive, ToPrimitive};
 
 use crate::{
     binread_flags::binread_flags,
     strings::{NullTerminatedString, StringEncoding},
-    CurrentOffset,
+    CurrentOffset, Warning,
 };
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+/// the size of a LinkInfo structure which consists of nothing but the header
+/// fields which are always present
+pub(crate) const MIN_LINK_INFO_SIZE: u32 = 0x1c;
+
 /// The LinkInfo structure specifies information necessary to resolve a
 /// linktarget if it is not found in its original location. This includes
 /// information about the volume that the target was stored on, the mapped
 /// drive letter, and a Universal Naming Convention (UNC)form of the path
 /// if one existed when the linkwas created. For more details about UNC
 /// paths, see [MS-DFSNM] section 2.2.1.4
-#[derive(Debug, BinRead, Getters)]
+#[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[getset(get = "pub")]
 #[allow(unused)]
 #[br(import(default_codepage: &'static Encoding))]
 pub struct LinkInfo {
     /// stores the beginning og this data structure
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     #[getset(skip)]
     link_info_offset: CurrentOffset,
     /// LinkInfoSize (4 bytes): A 32-bit, unsigned integer that specifies the
@@ -280,11 +290,203 @@ impl LinkInfo {
     pub fn common_path_suffix(&self) -> &str {
         self.common_path_suffix.as_ref()
     }
+
+    /// checks whether the strings of this structure look like paths, i.e.
+    /// whether the local base path is not empty and no string contains
+    /// control characters. Offsets which point to the wrong bytes usually
+    /// yield empty strings or strings with control characters.
+    pub(crate) fn has_plausible_strings(&self) -> bool {
+        let plausible = |s: &str| !s.chars().any(char::is_control);
+        let local_base_paths = [
+            self.local_base_path.as_deref(),
+            self.local_base_path_unicode.as_deref(),
+        ];
+        let common_path_suffixes = [
+            Some(self.common_path_suffix.as_str()),
+            self.common_path_suffix_unicode.as_deref(),
+        ];
+        local_base_paths
+            .into_iter()
+            .flatten()
+            .all(|s| !s.is_empty() && plausible(s))
+            && common_path_suffixes.into_iter().flatten().all(plausible)
+            && self
+                .common_network_relative_link
+                .as_ref()
+                .is_none_or(|link| plausible(link.net_name()))
+    }
+
+    /// returns the strings which are written in the code page when this
+    /// structure is serialized. The Unicode versions are preferred if present.
+    pub(crate) fn code_page_strings(&self) -> Vec<&str> {
+        let mut strings = vec![self
+            .common_path_suffix_unicode
+            .as_deref()
+            .unwrap_or(&self.common_path_suffix)];
+        strings.extend(
+            self.local_base_path_unicode
+                .as_deref()
+                .or(self.local_base_path.as_deref()),
+        );
+        strings.extend(self.volume_id.as_ref().map(VolumeID::volume_label));
+        if let Some(link) = &self.common_network_relative_link {
+            strings.push(link.net_name());
+            strings.extend(link.device_name());
+        }
+        strings
+    }
+
+    /// reads a LinkInfo structure of `link_info_size` bytes starting at
+    /// `link_info_offset`, whose offsets are relative to the start of the
+    /// file instead of the start of the structure. `file_offset` is the
+    /// distance of the structure from the start of the file. The offsets are
+    /// converted before the structure is parsed; afterwards, `reader` is
+    /// positioned behind the structure.
+    pub(crate) fn read_file_relative<R: Read + Seek>(
+        reader: &mut R,
+        link_info_offset: u64,
+        file_offset: u64,
+        link_info_size: u32,
+        default_codepage: &'static Encoding,
+    ) -> BinResult<Self> {
+        reader.seek(SeekFrom::Start(link_info_offset))?;
+        let mut bytes = vec![0; link_info_size as usize];
+        reader.read_exact(&mut bytes)?;
+
+        let header_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let header_end = if header_size >= 0x24 { 0x24 } else { 0x1c };
+        let header_end = header_end.min(bytes.len());
+        for field in bytes[12..header_end].chunks_exact_mut(4) {
+            let offset = u32::from_le_bytes(field.try_into().unwrap());
+            if offset != 0 {
+                // offsets in front of the structure cannot be file-relative;
+                // they are made invalid, so that parsing fails
+                let relative = u64::from(offset)
+                    .checked_sub(file_offset)
+                    .map_or(u32::MAX, |relative| relative as u32);
+                field.copy_from_slice(&relative.to_le_bytes());
+            }
+        }
+        Cursor::new(bytes).read_le_args((default_codepage,))
+    }
 }
 
-impl From<LinkInfo> for Vec<u8> {
-    fn from(_val: LinkInfo) -> Self {
-        unimplemented!()
+#[cfg(feature = "binwrite")]
+impl LinkInfo {
+    /// serializes this structure. If `encoding` is [`StringEncoding::Unicode`],
+    /// the paths are written both in `default_codepage` and in Unicode;
+    /// otherwise, they are written in the given code page only.
+    fn to_bytes(&self, encoding: &StringEncoding, default_codepage: &'static Encoding) -> Vec<u8> {
+        let (codepage, unicode) = match encoding {
+            StringEncoding::CodePage(codepage) => (*codepage, false),
+            StringEncoding::Unicode => (default_codepage, true),
+        };
+        let header_size: u32 = if unicode { 0x24 } else { 0x1c };
+        let mut flags = LinkInfoFlags::empty();
+        let mut data = Vec::new();
+        let offset = |data: &Vec<u8>| header_size + data.len() as u32;
+
+        let local_base_path = self
+            .local_base_path_unicode
+            .as_deref()
+            .or(self.local_base_path.as_deref());
+        let mut volume_id_offset = 0;
+        let mut local_base_path_offset = 0;
+        if let (Some(volume_id), Some(local_base_path)) = (&self.volume_id, local_base_path) {
+            flags |= LinkInfoFlags::VOLUME_ID_AND_LOCAL_BASE_PATH;
+            volume_id_offset = offset(&data);
+            data.extend(volume_id.to_bytes(codepage, unicode));
+            local_base_path_offset = offset(&data);
+            data.extend(encode_null_terminated(local_base_path, codepage));
+        }
+
+        let mut common_network_relative_link_offset = 0;
+        if let Some(link) = &self.common_network_relative_link {
+            flags |= LinkInfoFlags::COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX;
+            common_network_relative_link_offset = offset(&data);
+            data.extend(link.to_bytes(codepage, unicode));
+        }
+
+        let common_path_suffix = self
+            .common_path_suffix_unicode
+            .as_deref()
+            .unwrap_or(&self.common_path_suffix);
+        let common_path_suffix_offset = offset(&data);
+        data.extend(encode_null_terminated(common_path_suffix, codepage));
+
+        let mut unicode_offsets = Vec::new();
+        if unicode {
+            let local_base_path_offset_unicode = match local_base_path {
+                Some(local_base_path) if flags.has_volume_id_and_local_base_path() => {
+                    let offset = offset(&data);
+                    data.extend(encode_null_terminated(local_base_path, UTF_16LE));
+                    offset
+                }
+                _ => 0,
+            };
+            let common_path_suffix_offset_unicode = offset(&data);
+            data.extend(encode_null_terminated(common_path_suffix, UTF_16LE));
+            unicode_offsets = [
+                local_base_path_offset_unicode,
+                common_path_suffix_offset_unicode,
+            ]
+            .to_vec();
+        }
+
+        let mut bytes = Vec::new();
+        for field in [
+            header_size + data.len() as u32,
+            header_size,
+            flags.bits(),
+            volume_id_offset,
+            local_base_path_offset,
+            common_network_relative_link_offset,
+            common_path_suffix_offset,
+        ]
+        .into_iter()
+        .chain(unicode_offsets)
+        {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.extend(data);
+        bytes
+    }
+}
+
+/// writes the LinkInfo structure, recomputing its size, header size and
+/// offsets from the layout which is written. If the encoding is
+/// [`StringEncoding::Unicode`], the paths are written both in the system
+/// default code page, which is passed as second argument, and in Unicode;
+/// otherwise, they are written in the given code page only.
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for LinkInfo {
+    type Args<'a> = (StringEncoding, &'static Encoding);
+
+    fn write_options<W: std::io::Write + Seek>(
+        &self,
+        writer: &mut W,
+        _endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(&self.to_bytes(&args.0, args.1))?;
+        Ok(())
+    }
+}
+
+/// encodes `s` as NULL-terminated string. UTF-16LE strings are terminated
+/// by two zero bytes. Characters which cannot be represented in a code page
+/// are replaced by `?`.
+#[cfg(feature = "binwrite")]
+fn encode_null_terminated(s: &str, encoding: &'static Encoding) -> Vec<u8> {
+    if encoding == UTF_16LE {
+        s.encode_utf16()
+            .chain([0])
+            .flat_map(|c| c.to_le_bytes())
+            .collect()
+    } else {
+        let mut bytes = crate::strings::encode_lossy(s, encoding);
+        bytes.push(0);
+        bytes
     }
 }
 
@@ -334,11 +536,11 @@ impl LinkInfoFlags {
 /// resolving the link if the file is not found in its original location.
 #[derive(Clone, Debug, BinRead, Getters)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
-#[get(get = "pub")]
+#[getset(get = "pub")]
 #[allow(unused)]
 #[br(import(default_codepage: &'static Encoding))]
 pub struct VolumeID {
-    #[get(skip)]
+    #[getset(skip)]
     #[cfg_attr(feature = "serde", serde(skip))]
     start_offset: CurrentOffset,
     /// VolumeIDSize (4 bytes): A 32-bit, unsigned integer that specifies the
@@ -351,6 +553,7 @@ pub struct VolumeID {
 
     /// A 32-bit, unsigned integer that specifies the type of drive the link
     /// target is stored on.
+    #[br(map = |value: u32| DriveType::from(value))]
     drive_type: DriveType,
 
     /// A 32-bit, unsigned integer that specifies the drive serial number of
@@ -380,17 +583,36 @@ pub struct VolumeID {
     /// If the value of the VolumeLabelOffset field is not 0x00000014, this
     /// field MUST NOT be present; instead, the value of the VolumeLabelOffset
     /// field MUST be used to locate the volume label string.
-    #[br(if(volume_label_offset == 0x14))]
+    #[br(
+        if(volume_label_offset == 0x14),
+        assert(volume_label_offset_unicode.map(|o| o < volume_id_size).unwrap_or(true))
+    )]
     volume_label_offset_unicode: Option<u32>,
 
-    /// The label of the volume that the link target is stored on.
+    /// The label of the volume that the link target is stored on. If
+    /// `VolumeLabelOffsetUnicode` is present, the Unicode label is read from
+    /// there; otherwise, the code page label at `VolumeLabelOffset` is used.
     #[br(
+        seek_before(binrw::io::SeekFrom::Start(
+            (start_offset.as_ref() + volume_label_offset_unicode.unwrap_or(volume_label_offset)).into()
+        )),
         args({volume_label_offset_unicode.and(Some(StringEncoding::Unicode)).unwrap_or(StringEncoding::CodePage(default_codepage))}),
         map=|s: NullTerminatedString| s.to_string()
     )]
     #[getset(skip)]
     volume_label: String,
 
+    /// The bytes of this structure, as they have been read
+    #[br(
+        seek_before(binrw::io::SeekFrom::Start((*start_offset.as_ref()).into())),
+        count = volume_id_size,
+        restore_position
+    )]
+    #[getset(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw_bytes: Vec<u8>,
+
+    #[getset(skip)]
     #[cfg_attr(feature = "serde", serde(skip))]
     _next_offset: CurrentOffset,
 }
@@ -400,33 +622,131 @@ impl VolumeID {
     pub fn volume_label(&self) -> &str {
         self.volume_label.as_ref()
     }
+
+    /// The bytes of this structure, as they have been read. This includes
+    /// data which is not exposed by the other accessors, such as padding
+    /// after the volume label.
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.raw_bytes
+    }
 }
 
-impl From<VolumeID> for Vec<u8> {
-    fn from(_val: VolumeID) -> Self {
-        unimplemented!()
+#[cfg(feature = "binwrite")]
+impl VolumeID {
+    /// serializes this structure, storing the volume label either in
+    /// `codepage` or in Unicode
+    fn to_bytes(&self, codepage: &'static Encoding, unicode: bool) -> Vec<u8> {
+        let (label_offset, label_encoding) = if unicode {
+            (0x14u32, UTF_16LE)
+        } else {
+            (0x10u32, codepage)
+        };
+        let label = encode_null_terminated(&self.volume_label, label_encoding);
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(label_offset + label.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&u32::from(self.drive_type).to_le_bytes());
+        bytes.extend_from_slice(&self.drive_serial_number.to_le_bytes());
+        bytes.extend_from_slice(&label_offset.to_le_bytes());
+        if unicode {
+            bytes.extend_from_slice(&label_offset.to_le_bytes());
+        }
+        bytes.extend(label);
+        bytes
+    }
+}
+
+/// writes the VolumeID structure, storing the volume label either in the
+/// given code page or, if the flag is set, in Unicode
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for VolumeID {
+    type Args<'a> = (&'static Encoding, bool);
+
+    fn write_options<W: std::io::Write + Seek>(
+        &self,
+        writer: &mut W,
+        _endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(&self.to_bytes(args.0, args.1))?;
+        Ok(())
     }
 }
 
 /// A 32-bit, unsigned integer that specifies the type of drive the link target is stored on.
-#[derive(Clone, Debug, FromPrimitive, ToPrimitive, BinRead)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
-#[br(repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DriveType {
     /// The drive type cannot be determined.
-    DriveUnknown = 0x00,
+    DriveUnknown,
     /// The root path is invalid; for example, there is no volume mounted at the path.
-    DriveNoRootDir = 0x01,
+    DriveNoRootDir,
     /// The drive has removable media, such as a floppy drive, thumb drive, or flash card reader.
-    DriveRemovable = 0x02,
+    DriveRemovable,
     /// The drive has fixed media, such as a hard drive or flash drive.
-    DriveFixed = 0x03,
+    DriveFixed,
     /// The drive is a remote (network) drive.
-    DriveRemote = 0x04,
+    DriveRemote,
     /// The drive is a CD-ROM drive.
-    DriveCDRom = 0x05,
+    DriveCDRom,
     /// The drive is a RAM disk.
-    DriveRamdisk = 0x06,
+    DriveRamdisk,
+    /// A drive type which is not defined by the specification
+    Other(u32),
+}
+
+impl From<u32> for DriveType {
+    fn from(value: u32) -> Self {
+        match value {
+            0x00 => Self::DriveUnknown,
+            0x01 => Self::DriveNoRootDir,
+            0x02 => Self::DriveRemovable,
+            0x03 => Self::DriveFixed,
+            0x04 => Self::DriveRemote,
+            0x05 => Self::DriveCDRom,
+            0x06 => Self::DriveRamdisk,
+            value => Self::Other(value),
+        }
+    }
+}
+
+impl From<DriveType> for u32 {
+    fn from(value: DriveType) -> Self {
+        match value {
+            DriveType::DriveUnknown => 0x00,
+            DriveType::DriveNoRootDir => 0x01,
+            DriveType::DriveRemovable => 0x02,
+            DriveType::DriveFixed => 0x03,
+            DriveType::DriveRemote => 0x04,
+            DriveType::DriveCDRom => 0x05,
+            DriveType::DriveRamdisk => 0x06,
+            DriveType::Other(value) => value,
+        }
+    }
+}
+
+impl Display for DriveType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DriveUnknown => write!(f, "Unknown"),
+            Self::DriveNoRootDir => write!(f, "No root directory"),
+            Self::DriveRemovable => write!(f, "Removable"),
+            Self::DriveFixed => write!(f, "Fixed disk"),
+            Self::DriveRemote => write!(f, "Network drive"),
+            Self::DriveCDRom => write!(f, "CD-ROM"),
+            Self::DriveRamdisk => write!(f, "RAM disk"),
+            Self::Other(value) => write!(f, "Other ({value:#x})"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DriveType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
 }
 
 /// The CommonNetworkRelativeLink structure specifies information about the network location where a
@@ -439,7 +759,10 @@ pub enum DriveType {
 #[allow(unused)]
 #[br(import(default_codepage: &'static Encoding))]
 pub struct CommonNetworkRelativeLink {
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    start_offset: CurrentOffset,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
     /// CommonNetworkRelativeLinkSize (4 bytes): A 32-bit, unsigned integer
     /// that specifies the size, in bytes, of the CommonNetworkRelativeLink
     /// structure. This value MUST be greater than or equal to 0x00000014. All
@@ -540,15 +863,137 @@ pub struct CommonNetworkRelativeLink {
         map=|n: Option<NullTerminatedString>| n.map(|s| s.to_string())
     )]
     device_name_unicode: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    end_offset: CurrentOffset,
 }
 
-impl From<CommonNetworkRelativeLink> for Vec<u8> {
-    fn from(_val: CommonNetworkRelativeLink) -> Self {
-        unimplemented!()
+#[cfg(feature = "binwrite")]
+impl CommonNetworkRelativeLink {
+    /// serializes this structure. If `unicode` is set, the names are written
+    /// in Unicode in addition to `codepage`.
+    fn to_bytes(&self, codepage: &'static Encoding, unicode: bool) -> Vec<u8> {
+        let header_size: u32 = if unicode { 0x1c } else { 0x14 };
+        let device_name = self.device_name().filter(|_| self.has_valid_device());
+        let mut data = Vec::new();
+        let offset = |data: &Vec<u8>| header_size + data.len() as u32;
+
+        let net_name_offset = offset(&data);
+        data.extend(encode_null_terminated(self.net_name(), codepage));
+        let device_name_offset = match device_name {
+            Some(device_name) => {
+                let offset = offset(&data);
+                data.extend(encode_null_terminated(device_name, codepage));
+                offset
+            }
+            None => 0,
+        };
+
+        let mut unicode_offsets = Vec::new();
+        if unicode {
+            let net_name_offset_unicode = offset(&data);
+            data.extend(encode_null_terminated(self.net_name(), UTF_16LE));
+            let device_name_offset_unicode = match device_name {
+                Some(device_name) => {
+                    let offset = offset(&data);
+                    data.extend(encode_null_terminated(device_name, UTF_16LE));
+                    offset
+                }
+                None => 0,
+            };
+            unicode_offsets = [net_name_offset_unicode, device_name_offset_unicode].to_vec();
+        }
+
+        let network_provider_type = self
+            .network_provider_type
+            .map_or(0, |provider| provider as u32);
+        let mut bytes = Vec::new();
+        for field in [
+            header_size + data.len() as u32,
+            self.flags.bits(),
+            net_name_offset,
+            device_name_offset,
+            network_provider_type,
+        ]
+        .into_iter()
+        .chain(unicode_offsets)
+        {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.extend(data);
+        bytes
+    }
+}
+
+/// writes the CommonNetworkRelativeLink structure, storing the names in the
+/// given code page and, if the flag is set, in Unicode as well
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for CommonNetworkRelativeLink {
+    type Args<'a> = (&'static Encoding, bool);
+
+    fn write_options<W: std::io::Write + Seek>(
+        &self,
+        writer: &mut W,
+        _endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(&self.to_bytes(args.0, args.1))?;
+        Ok(())
     }
 }
 
 impl CommonNetworkRelativeLink {
+    /// returns the flags which specify the contents of the DeviceNameOffset
+    /// and NetProviderType fields
+    pub fn flags(&self) -> CommonNetworkRelativeLinkFlags {
+        self.flags
+    }
+
+    /// returns `true` if the ValidDevice flag is set, i.e. if this structure
+    /// contains a DeviceName
+    pub fn has_valid_device(&self) -> bool {
+        self.flags.has_valid_device()
+    }
+
+    /// returns `true` if the ValidNetType flag is set, i.e. if this structure
+    /// contains a NetworkProviderType
+    pub fn has_valid_net_type(&self) -> bool {
+        self.flags.has_valid_net_type()
+    }
+
+    /// returns the type of the network provider, if the ValidNetType flag is
+    /// set
+    pub fn network_provider_type(&self) -> Option<&NetworkProviderType> {
+        self.network_provider_type.as_ref()
+    }
+
+    /// returns the server share path, for example `\\server\share`. The
+    /// Unicode version is preferred if present.
+    pub fn net_name(&self) -> &str {
+        self.net_name_unicode.as_ref().unwrap_or(&self.net_name)
+    }
+
+    /// returns the device, for example the drive letter `D:`, if the
+    /// ValidDevice flag is set. The Unicode version is preferred if present.
+    pub fn device_name(&self) -> Option<&str> {
+        self.device_name_unicode
+            .as_ref()
+            .or(self.device_name.as_ref())
+            .map(|s| &s[..])
+    }
+
+    /// returns a warning if the CommonNetworkRelativeLinkSize differs from
+    /// the number of bytes which have been read for the fields
+    pub(crate) fn size_warning(&self) -> Option<Warning> {
+        let actual = self.end_offset.as_ref() - self.start_offset.as_ref();
+        (actual != self.common_network_relative_link_size).then_some(
+            Warning::CommonNetworkRelativeLinkSizeMismatch {
+                declared: self.common_network_relative_link_size,
+                actual,
+            },
+        )
+    }
+
     /// returns the name of this link
     pub fn name(&self) -> String {
         if self.flags.has_valid_device() {
@@ -598,9 +1043,12 @@ impl CommonNetworkRelativeLinkFlags {
 }
 
 /// A 32-bit, unsigned integer that specifies the type of network provider.
+/// The variants correspond to the `WNNC_NET_*` constants; for example,
+/// [`NetworkProviderType::Smb`] is `WNNC_NET_LANMAN` and
+/// [`NetworkProviderType::Dav`] is `WNNC_NET_DAV`.
 /// <https://learn.microsoft.com/de-de/windows/win32/api/winbase/ns-winbase-file_remote_protocol_info>
 #[allow(missing_docs)]
-#[derive(Clone, Debug, FromPrimitive, ToPrimitive, BinRead)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive, BinRead)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[br(repr(u32))]
 pub enum NetworkProviderType {
@@ -672,3 +1120,56 @@ pub enum NetworkProviderType {
     MsNfs = 0x420000,
     Google = 0x430000,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinReaderExt;
+    use encoding_rs::WINDOWS_1252;
+
+    use super::{DriveType, VolumeID};
+
+    fn volume_id(label_offset: u32, unicode_offset: Option<u32>, data: &[u8]) -> Vec<u8> {
+        let header_size = if unicode_offset.is_some() { 0x14 } else { 0x10 };
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(header_size + data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&0x1234_5678u32.to_le_bytes());
+        bytes.extend_from_slice(&label_offset.to_le_bytes());
+        if let Some(offset) = unicode_offset {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_volume_label_prefers_unicode() {
+        // an unusable ANSI label, followed by the Unicode label at 0x18
+        let mut data = b"??\0\0".to_vec();
+        for c in "ボリューム".encode_utf16().chain([0]) {
+            data.extend_from_slice(&c.to_le_bytes());
+        }
+        let mut cursor = Cursor::new(volume_id(0x14, Some(0x18), &data));
+        let volume_id: VolumeID = cursor.read_le_args((WINDOWS_1252,)).unwrap();
+        assert_eq!(volume_id.volume_label(), "ボリューム");
+    }
+
+    #[test]
+    fn test_volume_label_ansi() {
+        let mut cursor = Cursor::new(volume_id(0x10, None, b"OSDisk\0"));
+        let volume_id: VolumeID = cursor.read_le_args((WINDOWS_1252,)).unwrap();
+        assert_eq!(volume_id.volume_label(), "OSDisk");
+    }
+
+    #[test]
+    fn test_unknown_drive_type() {
+        let mut bytes = volume_id(0x10, None, b"OSDisk\0");
+        bytes[4..8].copy_from_slice(&0x42u32.to_le_bytes());
+        let volume_id: VolumeID = Cursor::new(bytes).read_le_args((WINDOWS_1252,)).unwrap();
+        assert_eq!(*volume_id.drive_type(), DriveType::Other(0x42));
+        assert_eq!(u32::from(*volume_id.drive_type()), 0x42);
+        assert_eq!(DriveType::from(3).to_string(), "Fixed disk");
+    }
+}
@@ -5,20 +5,24 @@ use log::{debug, error, info, trace, warn};
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use crate::{generic_types::idlist::IdList, itemid::ItemID};
+use crate::{
+    generic_types::idlist::IdList,
+    itemid::{ItemID, ItemIdDecoder},
+};
 
 /// The LinkTargetIDList structure specifies the target of the link. The presence of this optional
 /// structure is specified by the HasLinkTargetIDList bit (LinkFlagssection 2.1.1) in the
 /// ShellLinkHeader(section2.1).
 #[derive(Clone, Debug, Default, BinRead)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
+#[br(import(max_items: usize))]
 pub struct LinkTargetIdList {
     /// The size, in bytes, of the IDList field.
     pub size: u16,
     /// A stored IDList structure (section 2.2.1), which contains the item ID list. An IDList
     /// structure conforms to the following ABNF \[RFC5234\]:
     ///   `IDLIST = *ITEMID TERMINALID`
-    #[br(args(size))]
+    #[br(args(size, max_items))]
     id_list: IdList,
 }
 
@@ -27,4 +31,44 @@ impl LinkTargetIdList {
     pub fn id_list(&self) -> &Vec<ItemID> {
         self.id_list.item_id_list()
     }
+
+    /// assembles the path which is described by this list. See
+    /// [`IdList::to_path`]
+    pub fn to_path(&self) -> Option<String> {
+        self.id_list.to_path()
+    }
+
+    /// assembles the path which is described by this list, using custom
+    /// decoders for the items. See [`IdList::to_path_with`]
+    pub fn to_path_with(&self, decoders: &[&dyn ItemIdDecoder]) -> Option<String> {
+        self.id_list.to_path_with(decoders)
+    }
+}
+
+/// writes the IDListSize, which is computed from the items of the list,
+/// followed by the list
+#[cfg(feature = "binwrite")]
+impl binrw::BinWrite for LinkTargetIdList {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        endian: binrw::Endian,
+        args: Self::Args<'_>,
+    ) -> binrw::BinResult<()> {
+        let size =
+            u16::try_from(self.id_list.byte_size()).map_err(|_| binrw::Error::AssertFail {
+                pos: writer.stream_position().unwrap_or_default(),
+                message: "IDList is larger than 65535 bytes".to_string(),
+            })?;
+        size.write_options(writer, endian, args)?;
+        self.id_list.write_options(writer, endian, args)
+    }
+}
+
+impl AsRef<IdList> for LinkTargetIdList {
+    fn as_ref(&self) -> &IdList {
+        &self.id_list
+    }
 }
@@ -0,0 +1,136 @@
+use getset::CopyGetters;
+
+/// Options which control how a shell link is parsed by
+/// [`ShellLink::open_with_options`](crate::ShellLink::open_with_options)
+#[derive(Clone, Copy, Debug, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct OpenOptions {
+    /// returns whether a single trailing null character is removed from the
+    /// strings of the [`StringData`](crate::StringData) structure. Some
+    /// creators count a null terminator as part of the string; the default
+    /// is `true`.
+    trim_trailing_null: bool,
+
+    /// returns whether Unicode strings of the [`StringData`](crate::StringData)
+    /// structure, whose CountCharacters field obviously contains a count of
+    /// bytes instead of characters, are read accordingly. Some creators
+    /// violate the specification this way; the default is `false`.
+    detect_byte_counts: bool,
+
+    /// returns whether Unicode strings of the [`StringData`](crate::StringData)
+    /// structure, which are obviously encoded as UTF-16BE instead of UTF-16LE,
+    /// are read accordingly. Some creators violate the specification this
+    /// way; the default is `false`.
+    detect_byte_order: bool,
+
+    /// returns the maximum number of ItemIDs which are read from the
+    /// [`LinkTargetIdList`](crate::LinkTargetIdList). A list with more items
+    /// is rejected, to protect against hostile files; the default is `256`.
+    max_idlist_items: usize,
+
+    /// returns whether the offsets of the [`LinkInfo`](crate::LinkInfo)
+    /// structure are checked for being relative to the start of the file
+    /// instead of the start of the structure. Some creators violate the
+    /// specification this way; the default is `false`.
+    detect_file_relative_offsets: bool,
+
+    /// returns whether the code page of the
+    /// [`ConsoleFEDataBlock`](crate::extradata::console_fe_data::ConsoleFEDataBlock)
+    /// is used to decode the strings of the [`StringData`](crate::StringData)
+    /// structure, if the link is not Unicode encoded; the default is `false`.
+    use_console_code_page: bool,
+
+    /// returns whether the bytes following the ExtraData are read as the
+    /// trailer of the link (see
+    /// [`ShellLink::trailer`](crate::ShellLink::trailer)). If this is set,
+    /// the reader is read to its end; the default is `false`.
+    /// [`ShellLink::open`](crate::ShellLink::open) and
+    /// [`ShellLink::from_bytes`](crate::ShellLink::from_bytes) always read
+    /// the trailer.
+    read_trailer: bool,
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self {
+            trim_trailing_null: true,
+            detect_byte_counts: false,
+            detect_byte_order: false,
+            max_idlist_items: 256,
+            detect_file_relative_offsets: false,
+            use_console_code_page: false,
+            read_trailer: false,
+        }
+    }
+}
+
+impl OpenOptions {
+    /// configures whether a single trailing null character is removed from
+    /// the strings of the [`StringData`](crate::StringData) structure
+    pub fn with_trim_trailing_null(mut self, trim_trailing_null: bool) -> Self {
+        self.trim_trailing_null = trim_trailing_null;
+        self
+    }
+
+    /// configures whether Unicode strings of the
+    /// [`StringData`](crate::StringData) structure are checked for a
+    /// CountCharacters field which contains a count of bytes. Such a string
+    /// is detected if reading twice as many bytes would run past the end of
+    /// the file, or would include control characters following a string of
+    /// printable characters.
+    pub fn with_detect_byte_counts(mut self, detect_byte_counts: bool) -> Self {
+        self.detect_byte_counts = detect_byte_counts;
+        self
+    }
+
+    /// configures whether Unicode strings of the
+    /// [`StringData`](crate::StringData) structure are checked for being
+    /// encoded as UTF-16BE. Such a string is detected if the low byte of all
+    /// its characters is zero, while swapping the bytes yields printable
+    /// characters.
+    pub fn with_detect_byte_order(mut self, detect_byte_order: bool) -> Self {
+        self.detect_byte_order = detect_byte_order;
+        self
+    }
+
+    /// configures the maximum number of ItemIDs which are read from the
+    /// [`LinkTargetIdList`](crate::LinkTargetIdList). If the list contains
+    /// more items, opening the shell link fails.
+    pub fn with_max_idlist_items(mut self, max_idlist_items: usize) -> Self {
+        self.max_idlist_items = max_idlist_items;
+        self
+    }
+
+    /// configures whether the offsets of the [`LinkInfo`](crate::LinkInfo)
+    /// structure are checked for being relative to the start of the file.
+    /// If the LinkInfo cannot be read using offsets relative to the start of
+    /// the structure, or if this yields strings containing control
+    /// characters, it is read again using file-relative offsets. The
+    /// file-relative interpretation is used if its strings are plausible.
+    pub fn with_detect_file_relative_offsets(mut self, detect_file_relative_offsets: bool) -> Self {
+        self.detect_file_relative_offsets = detect_file_relative_offsets;
+        self
+    }
+
+    /// configures whether the code page of the
+    /// [`ConsoleFEDataBlock`](crate::extradata::console_fe_data::ConsoleFEDataBlock)
+    /// is used as a hint for the system default code page. If the link is not
+    /// Unicode encoded and contains such a block with a supported code page,
+    /// the strings of the [`StringData`](crate::StringData) structure are
+    /// decoded using this code page instead of the encoding which has been
+    /// passed to [`ShellLink::open_with_options`](crate::ShellLink::open_with_options).
+    /// The [`LinkInfo`](crate::LinkInfo) is not affected.
+    pub fn with_use_console_code_page(mut self, use_console_code_page: bool) -> Self {
+        self.use_console_code_page = use_console_code_page;
+        self
+    }
+
+    /// configures whether the bytes following the ExtraData are read as the
+    /// trailer of the link. This should only be enabled if the reader
+    /// contains nothing but the link, and not e.g. several links one after
+    /// another.
+    pub fn with_read_trailer(mut self, read_trailer: bool) -> Self {
+        self.read_trailer = read_trailer;
+        self
+    }
+}
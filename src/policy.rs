@@ -0,0 +1,102 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::ShellLink;
+
+/// A set of rules which shell links must follow, e.g. when they are
+/// generated by a provisioning pipeline. See [`ShellLink::check_policy`].
+///
+/// ```
+/// use lnk::{PolicyViolation, ShellLink, ShortcutPolicy};
+///
+/// let policy = ShortcutPolicy::new()
+///     .require_name()
+///     .allowed_root(r"C:\Program Files")
+///     .forbid_arguments();
+/// let mut shortcut = ShellLink::default();
+/// shortcut.set_arguments(Some("--debug".to_string()));
+/// assert_eq!(
+///     shortcut.check_policy(&policy),
+///     vec![
+///         PolicyViolation::MissingName,
+///         PolicyViolation::TargetNotAllowed { target: None },
+///         PolicyViolation::HasArguments,
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ShortcutPolicy {
+    require_name: bool,
+    allowed_roots: Vec<String>,
+    forbid_arguments: bool,
+}
+
+impl ShortcutPolicy {
+    /// creates a new policy, which has no rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// requires links to have a name, which is shown as comment of the link
+    pub fn require_name(mut self) -> Self {
+        self.require_name = true;
+        self
+    }
+
+    /// allows link targets within the directory `root`. If this is called
+    /// at least once, link targets must lie within one of the allowed roots
+    /// (see [`ShellLink::target_within_roots`]); otherwise, every link target
+    /// is allowed.
+    pub fn allowed_root(mut self, root: impl Into<String>) -> Self {
+        self.allowed_roots.push(root.into());
+        self
+    }
+
+    /// forbids links to have command line arguments
+    pub fn forbid_arguments(mut self) -> Self {
+        self.forbid_arguments = true;
+        self
+    }
+
+    /// returns the rules of this policy which `link` violates
+    pub(crate) fn check(&self, link: &ShellLink) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        if self.require_name && link.string_data().name_string().is_none() {
+            violations.push(PolicyViolation::MissingName);
+        }
+        if !self.allowed_roots.is_empty() {
+            let roots: Vec<&str> = self.allowed_roots.iter().map(String::as_str).collect();
+            if link.target_within_roots(&roots) != Some(true) {
+                violations.push(PolicyViolation::TargetNotAllowed {
+                    target: link.resolve_target(),
+                });
+            }
+        }
+        if self.forbid_arguments && link.string_data().command_line_arguments().is_some() {
+            violations.push(PolicyViolation::HasArguments);
+        }
+        violations
+    }
+}
+
+/// A rule of a [`ShortcutPolicy`] which a shell link violates
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[non_exhaustive]
+pub enum PolicyViolation {
+    /// the policy requires a name, but the link has none
+    #[error("The shell link has no name")]
+    MissingName,
+
+    /// the link target does not lie within one of the allowed roots
+    #[error("The link target {target:?} is not within an allowed root")]
+    TargetNotAllowed {
+        /// the link target, or `None` if it cannot be determined
+        target: Option<String>,
+    },
+
+    /// the policy forbids arguments, but the link has some
+    #[error("The shell link has command line arguments")]
+    HasArguments,
+}
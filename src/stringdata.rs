@@ -1,27 +1,34 @@
 use std::fmt::Display;
 
-use crate::{strings::*, LinkFlags};
+use crate::{strings::*, LinkFlags, OpenOptions};
 use binrw::BinRead;
 #[cfg(feature = "binwrite")]
 use binrw::BinWrite;
 use encoding_rs::Encoding;
 use getset::{Getters, Setters};
+#[cfg(feature = "serde")]
 use serde::Serialize;
 
 /// StringData refers to a set of structures that convey user interface and
 /// path identification information. The presence of these optional structures
 /// is controlled by LinkFlags (section 2.1.1) in the ShellLinkHeader
 /// (section 2.1).
-#[derive(BinRead, Default, Getters, Setters, Debug, Serialize)]
+#[derive(BinRead, Clone, Default, Getters, Setters, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "binwrite", derive(BinWrite))]
 #[getset(get = "pub", set = "pub")]
-#[brw(import(link_flags: LinkFlags, encoding: &'static Encoding))]
+#[br(import(link_flags: LinkFlags, encoding: &'static Encoding, options: OpenOptions))]
+#[cfg_attr(
+    feature = "binwrite",
+    bw(import(link_flags: LinkFlags, encoding: &'static Encoding))
+)]
 pub struct StringData {
     /// NAME_STRING: An optional structure that specifies a description of the
     /// shortcut that is displayed to end users to identify the purpose of the
     /// shell link. This structure MUST be present if the HasName flag is set.
-    #[brw(args(link_flags, LinkFlags::HAS_NAME, encoding))]
+    #[br(args(link_flags, LinkFlags::HAS_NAME, encoding, options))]
     #[br(parse_with = parse_sized_string)]
+    #[cfg_attr(feature="binwrite", bw(args(link_flags, LinkFlags::HAS_NAME, encoding)))]
     #[cfg_attr(feature="binwrite", bw(write_with=write_sized_string))]
     name_string: Option<String>,
 
@@ -29,32 +36,36 @@ pub struct StringData {
     /// link target relative to the file that contains the shell link. When
     /// specified, this string SHOULD be used when resolving the link. This
     /// structure MUST be present if the HasRelativePath flag is set.
-    #[brw(args(link_flags, LinkFlags::HAS_RELATIVE_PATH, encoding))]
+    #[br(args(link_flags, LinkFlags::HAS_RELATIVE_PATH, encoding, options))]
     #[br(parse_with = parse_sized_string)]
+    #[cfg_attr(feature="binwrite", bw(args(link_flags, LinkFlags::HAS_RELATIVE_PATH, encoding)))]
     #[cfg_attr(feature="binwrite", bw(write_with=write_sized_string))]
     relative_path: Option<String>,
 
     /// WORKING_DIR: An optional structure that specifies the file system path
     /// of the working directory to be used when activating the link target.
     /// This structure MUST be present if the HasWorkingDir flag is set.
-    #[brw(args(link_flags, LinkFlags::HAS_WORKING_DIR, encoding))]
+    #[br(args(link_flags, LinkFlags::HAS_WORKING_DIR, encoding, options))]
     #[br(parse_with = parse_sized_string)]
+    #[cfg_attr(feature="binwrite", bw(args(link_flags, LinkFlags::HAS_WORKING_DIR, encoding)))]
     #[cfg_attr(feature="binwrite", bw(write_with=write_sized_string))]
     working_dir: Option<String>,
 
     /// COMMAND_LINE_ARGUMENTS: An optional structure that stores the
     /// command-line arguments that are specified when activating the link
     /// target. This structure MUST be present if the HasArguments flag is set.
-    #[brw(args(link_flags, LinkFlags::HAS_ARGUMENTS, encoding))]
+    #[br(args(link_flags, LinkFlags::HAS_ARGUMENTS, encoding, options))]
     #[br(parse_with = parse_sized_string)]
+    #[cfg_attr(feature="binwrite", bw(args(link_flags, LinkFlags::HAS_ARGUMENTS, encoding)))]
     #[cfg_attr(feature="binwrite", bw(write_with=write_sized_string))]
     command_line_arguments: Option<String>,
 
     /// ICON_LOCATION: An optional structure that specifies the location of the
     /// icon to be used when displaying a shell link item in an icon view. This
     /// structure MUST be present if the HasIconLocation flag is set.
-    #[brw(args(link_flags, LinkFlags::HAS_ICON_LOCATION, encoding))]
+    #[br(args(link_flags, LinkFlags::HAS_ICON_LOCATION, encoding, options))]
     #[br(parse_with = parse_sized_string)]
+    #[cfg_attr(feature="binwrite", bw(args(link_flags, LinkFlags::HAS_ICON_LOCATION, encoding)))]
     #[cfg_attr(feature="binwrite", bw(write_with=write_sized_string))]
     icon_location: Option<String>,
 }
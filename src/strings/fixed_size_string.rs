@@ -1,7 +1,6 @@
 use binrw::BinRead;
 use core::fmt::Display;
 use encoding_rs::Encoding;
-use substring::Substring;
 
 /// represents a string that is stored in a buffer of a fixed size
 #[derive(Clone, Debug)]
@@ -30,8 +29,10 @@ impl BinRead for FixedSizeString {
             });
         }
         let mut res = cow.to_string();
-        if let Some(last_character) = res.find('\u{0000}') {
-            res = res.substring(0, last_character).to_string();
+        // `find` returns a byte offset, which may differ from the character
+        // offset if the string contains non-ASCII characters
+        if let Some(end) = res.find('\u{0000}') {
+            res.truncate(end);
         }
         Ok(Self(res))
     }
@@ -55,3 +56,27 @@ impl FixedSizeString {
         self.0.is_empty()
     }
 }
+
+/// encodes `s` using `encoding` into a buffer of exactly `size` bytes, which
+/// is padded with NULs. Like in [`FixedSizeString`], a string which fills the
+/// whole buffer has no NUL terminator; a string which is too long is
+/// truncated.
+#[cfg(feature = "binwrite")]
+pub(crate) fn fixed_size_bytes(s: &str, size: usize, encoding: &'static Encoding) -> Vec<u8> {
+    let encode = |s: &str| -> Vec<u8> {
+        if encoding == encoding_rs::UTF_16LE {
+            s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+        } else {
+            encoding.encode(s).0.into_owned()
+        }
+    };
+    let mut s = s;
+    let mut bytes = encode(s);
+    while bytes.len() > size {
+        let end = s.char_indices().last().map_or(0, |(index, _)| index);
+        s = &s[..end];
+        bytes = encode(s);
+    }
+    bytes.resize(size, 0);
+    bytes
+}
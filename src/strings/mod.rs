@@ -18,6 +18,48 @@ macro_rules! support_encoding {
     };
 }
 
+/// returns the encoding of the Windows code page `code_page` (e.g. `932` for
+/// Shift_JIS), if it is supported by [`encoding_rs`]
+pub(crate) fn encoding_for_code_page(code_page: u32) -> Option<Encoding> {
+    let encoding = match code_page {
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1252 => encoding_rs::WINDOWS_1252,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        65001 => encoding_rs::UTF_8,
+        _ => return None,
+    };
+    Some(encoding)
+}
+
+/// encodes `s` in the code page `encoding`. Unlike
+/// [`encoding_rs::Encoding::encode`], which emits HTML numeric character
+/// references, characters which cannot be represented are replaced by `?`.
+#[cfg(feature = "binwrite")]
+pub(crate) fn encode_lossy(s: &str, encoding: Encoding) -> Vec<u8> {
+    let (bytes, _, had_errors) = encoding.encode(s);
+    if !had_errors {
+        return bytes.into_owned();
+    }
+    let mut buffer = [0; 4];
+    s.chars()
+        .flat_map(|c| match encoding.encode(c.encode_utf8(&mut buffer)) {
+            (_, _, true) => vec![b'?'],
+            (bytes, _, false) => bytes.into_owned(),
+        })
+        .collect()
+}
+
 /// this module reexports all statics from `encoding_rs`
 pub mod encoding {
     #![allow(missing_docs)]
@@ -1,18 +1,30 @@
+use std::io::SeekFrom;
+
 use binrw::{BinReaderExt, BinResult};
-use encoding_rs::{Encoding, UTF_16LE};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE};
 use log::trace;
 
 #[cfg(feature = "binwrite")]
 use binrw::BinWrite;
 
-use crate::{LinkFlags, StringEncoding};
+use crate::{LinkFlags, OpenOptions, StringEncoding};
 
-/// reads a sized string from `reader` and converts it into a [`String`]
+/// reads a sized string from `reader` and converts it into a [`String`].
+/// `options` controls how deviations from the specification are handled:
+///
+/// * if `trim_trailing_null` is set, a single null character at the end of
+///   the string is removed.
+/// * if `detect_byte_counts` is set, the size of a Unicode string is
+///   interpreted as a count of bytes if it obviously is one (see
+///   [`is_byte_counted`]).
+/// * if `detect_byte_order` is set, a Unicode string is decoded as UTF-16BE
+///   if it obviously is not UTF-16LE (see [`is_big_endian`]).
 #[binrw::parser(reader: reader)]
 pub fn parse_sized_string(
     link_flags: LinkFlags,
     expected_flag: LinkFlags,
     encoding: &'static Encoding,
+    options: OpenOptions,
 ) -> BinResult<Option<String>> {
     if link_flags.contains(expected_flag) {
         log::info!("reading string at {}", reader.stream_position()?);
@@ -26,7 +38,7 @@ pub fn parse_sized_string(
 
         log::info!("characters: {count_characters}");
 
-        match encoding {
+        let mut s = match encoding {
             StringEncoding::CodePage(default_encoding) => {
                 let mut buffer = vec![0; count_characters.into()];
                 reader.read_exact(&mut buffer)?;
@@ -39,28 +51,91 @@ pub fn parse_sized_string(
                         ),
                     });
                 }
-                Ok(Some(cow.to_string()))
+                cow.to_string()
             }
             StringEncoding::Unicode => {
-                let mut buffer = vec![0; (count_characters * 2).into()];
-                reader.read_exact(&mut buffer)?;
-                let (cow, _, had_errors) = UTF_16LE.decode(&buffer);
+                let start = reader.stream_position()?;
+                let mut buffer = vec![0; usize::from(count_characters) * 2];
+                let byte_counted = match reader.read_exact(&mut buffer) {
+                    Ok(()) => options.detect_byte_counts() && is_byte_counted(&buffer),
+                    Err(why)
+                        if options.detect_byte_counts()
+                            && why.kind() == std::io::ErrorKind::UnexpectedEof =>
+                    {
+                        true
+                    }
+                    Err(why) => return Err(why.into()),
+                };
+                if byte_counted {
+                    log::warn!(
+                        "the size of the string at 0x{start:08x} seems to be a count of bytes"
+                    );
+                    buffer.truncate(count_characters.into());
+                    reader.seek(SeekFrom::Start(start))?;
+                    reader.read_exact(&mut buffer)?;
+                }
+                let unicode = if options.detect_byte_order() && is_big_endian(&buffer) {
+                    log::warn!("the string at 0x{start:08x} seems to be encoded as UTF-16BE");
+                    UTF_16BE
+                } else {
+                    UTF_16LE
+                };
+                let (cow, _, had_errors) = unicode.decode(&buffer);
                 if had_errors {
                     return Err(binrw::error::Error::AssertFail {
                         pos: reader.stream_position()?,
                         message: format!(
-                            "unable to decode String to UTF-16LE from buffer {buffer:?}"
+                            "unable to decode String to {} from buffer {buffer:?}",
+                            unicode.name()
                         ),
                     });
                 }
-                Ok(Some(cow.to_string()))
+                cow.to_string()
             }
+        };
+        if options.trim_trailing_null() && s.ends_with('\0') {
+            s.pop();
         }
+        Ok(Some(s))
     } else {
         Ok(None)
     }
 }
 
+/// checks whether `buffer`, which has been read as a Unicode string of half
+/// its size in characters, actually contains a string of half its size in
+/// bytes, followed by other data. This is assumed if the first half consists
+/// of printable characters only, while the second half contains control
+/// characters other than a single trailing null.
+fn is_byte_counted(buffer: &[u8]) -> bool {
+    let units: Vec<u16> = buffer
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    if units.is_empty() || !units.len().is_multiple_of(2) {
+        return false;
+    }
+    let (string, rest) = units.split_at(units.len() / 2);
+    let is_control = |u: &u16| *u < 0x20;
+    let rest = rest.strip_suffix(&[0]).unwrap_or(rest);
+    !string.iter().any(is_control) && rest.iter().any(is_control)
+}
+
+/// checks whether `buffer`, which is expected to contain a UTF-16LE string,
+/// actually contains a UTF-16BE string. This is assumed if the low byte of
+/// every character is zero, while the characters would be printable if the
+/// bytes were swapped. A single trailing null is ignored.
+fn is_big_endian(buffer: &[u8]) -> bool {
+    let units: Vec<[u8; 2]> = buffer.chunks_exact(2).map(|c| [c[0], c[1]]).collect();
+    let units = units.strip_suffix(&[[0, 0]]).unwrap_or(&units);
+    !units.is_empty()
+        && units.iter().all(|[low, _]| *low == 0)
+        && units
+            .iter()
+            .map(|unit| u16::from_be_bytes(*unit))
+            .all(|u| u >= 0x20 && !(0xd800..0xe000).contains(&u))
+}
+
 /// converts a [`String`] to a sized string and writes it
 #[cfg(feature = "binwrite")]
 #[cfg_attr(feature="binwrite", binrw::writer(writer: writer))]
@@ -73,23 +148,90 @@ pub fn write_sized_string(
     if link_flags.contains(expected_flag) {
         assert!(s.is_some());
         let s = s.as_ref().expect("the flags indicate that there should be a value, but there is none");
-        let count_characters = u16::try_from(s.len()).map_err(|_| binrw::Error::Custom {
-            pos: writer.stream_position().unwrap(),
-            err: Box::new("String is too long to be written"),
-        })?;
-        count_characters.write_le(writer)?;
 
+        // CountCharacters is the number of bytes in the code page, or the
+        // number of UTF-16 code units, respectively
         let encoding = StringEncoding::from(link_flags, encoding);
-        match encoding {
-            StringEncoding::CodePage(cp) => cp.encode(&s).0.write(writer)?,
+        let (count, bytes) = match encoding {
+            StringEncoding::CodePage(cp) => {
+                // encoding_rs replaces unmappable characters by HTML numeric
+                // character references, which must not end up in the link
+                let (bytes, _, had_errors) = cp.encode(s);
+                if had_errors {
+                    return Err(binrw::Error::Custom {
+                        pos: writer.stream_position()?,
+                        err: Box::new(format!("{s:?} cannot be represented in {}", cp.name())),
+                    });
+                }
+                (bytes.len(), bytes.into_owned())
+            }
             StringEncoding::Unicode => {
-                let v: Vec<_> = s.encode_utf16().collect();
-                v.write_le(writer)?
+                let units: Vec<_> = s.encode_utf16().collect();
+                let bytes = units.iter().flat_map(|u| u.to_le_bytes()).collect();
+                (units.len(), bytes)
             }
         };
+        let pos = writer.stream_position()?;
+        let count_characters = u16::try_from(count).map_err(|_| binrw::Error::Custom {
+            pos,
+            err: Box::new("String is too long to be written"),
+        })?;
+        count_characters.write_le(writer)?;
+        bytes.write(writer)?;
         Ok(())
     } else {
         assert!(s.is_none());
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::Endian;
+    use encoding_rs::WINDOWS_1252;
+
+    use super::*;
+
+    fn parse(bytes: &[u8], trim_trailing_null: bool) -> Option<String> {
+        parse_sized_string(
+            &mut Cursor::new(bytes),
+            Endian::Little,
+            (
+                LinkFlags::HAS_NAME,
+                LinkFlags::HAS_NAME,
+                WINDOWS_1252,
+                OpenOptions::default().with_trim_trailing_null(trim_trailing_null),
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_trim_trailing_null() {
+        assert_eq!(parse(b"\x05\x00a\0bc\0", true), Some("a\0bc".to_string()));
+        assert_eq!(
+            parse(b"\x05\x00a\0bc\0", false),
+            Some("a\0bc\0".to_string())
+        );
+        assert_eq!(
+            parse(b"\x06\x00a\0bc\0\0", true),
+            Some("a\0bc\0".to_string())
+        );
+    }
+
+    #[cfg(feature = "binwrite")]
+    #[test]
+    fn test_unrepresentable_string() {
+        let mut writer = Cursor::new(Vec::new());
+        let result = write_sized_string(
+            &Some("\u{6587}\u{66f8}".to_string()),
+            &mut writer,
+            Endian::Little,
+            (LinkFlags::HAS_NAME, LinkFlags::HAS_NAME, WINDOWS_1252),
+        );
+        assert!(matches!(result, Err(binrw::Error::Custom { pos: 0, .. })));
+        assert!(writer.into_inner().is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::{fmt::Debug, io::Cursor};
+
+use binrw::{BinResult, BinWrite};
+use getset::CopyGetters;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{LinkFlags, ShellLink};
+
+/// A structure of a shell link, as reported by [`structural_diff`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Structure {
+    /// the [`ShellLinkHeader`](crate::ShellLinkHeader)
+    Header,
+
+    /// the [`LinkTargetIdList`](crate::LinkTargetIdList)
+    LinkTargetIdList,
+
+    /// the [`LinkInfo`](crate::LinkInfo)
+    LinkInfo,
+
+    /// the [`StringData`](crate::StringData)
+    StringData,
+
+    /// a block of the [`ExtraData`](crate::ExtraData), identified by its
+    /// signature
+    ExtraDataBlock(u32),
+}
+
+/// The way a structure has been changed, see [`structural_diff`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ChangeKind {
+    /// the structure is present in the second link only
+    Added,
+
+    /// the structure is present in the first link only
+    Removed,
+
+    /// the structure is present in both links, but its bytes differ
+    Modified,
+}
+
+/// A structure which differs between two shell links, see
+/// [`structural_diff`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[getset(get_copy = "pub")]
+pub struct StructuralChange {
+    /// returns the structure which has been changed
+    structure: Structure,
+
+    /// returns how the structure has been changed
+    kind: ChangeKind,
+}
+
+/// compares the serialized structures of `a` and `b`, and returns the
+/// structures which have been added, removed or modified in `b`. ExtraData
+/// blocks are matched by their signature.
+///
+/// Structures are compared by the bytes they would be written as. Structures
+/// which cannot be serialized are compared by their parsed fields instead.
+#[cfg_attr(feature = "binwrite", stability::unstable(feature = "save"))]
+pub fn structural_diff(a: &ShellLink, b: &ShellLink) -> Vec<StructuralChange> {
+    let mut changes = Vec::new();
+    let mut push = |structure, kind| changes.push(StructuralChange { structure, kind });
+
+    if differs(
+        a.header(),
+        to_bytes(a.header(), ()),
+        b.header(),
+        to_bytes(b.header(), ()),
+    ) {
+        push(Structure::Header, ChangeKind::Modified);
+    }
+
+    let id_lists = [a, b].map(|link| {
+        link.linktarget_id_list()
+            .as_ref()
+            .filter(|_| {
+                link.header()
+                    .link_flags()
+                    .contains(LinkFlags::HAS_LINK_TARGET_ID_LIST)
+            })
+            .map(|id_list| (id_list, to_bytes(id_list, ())))
+    });
+    if let Some(kind) = compare_optional(id_lists) {
+        push(Structure::LinkTargetIdList, kind);
+    }
+
+    let link_infos = [a, b].map(|link| {
+        link.link_info()
+            .as_ref()
+            .filter(|_| {
+                link.header()
+                    .link_flags()
+                    .contains(LinkFlags::HAS_LINK_INFO)
+            })
+            .map(|info| {
+                let args = (link.link_info_encoding(), link.code_page);
+                (info, to_bytes(info, args))
+            })
+    });
+    if let Some(kind) = compare_optional(link_infos) {
+        push(Structure::LinkInfo, kind);
+    }
+
+    let string_data_bytes = |link: &ShellLink| {
+        to_bytes(
+            link.string_data(),
+            (*link.header().link_flags(), link.encoding),
+        )
+    };
+    if differs(
+        a.string_data(),
+        string_data_bytes(a),
+        b.string_data(),
+        string_data_bytes(b),
+    ) {
+        push(Structure::StringData, ChangeKind::Modified);
+    }
+
+    let a_blocks = a.extra_data().blocks();
+    let b_blocks = b.extra_data().blocks();
+    let mut matched = vec![false; b_blocks.len()];
+    for (a_index, a_block) in a_blocks.iter().enumerate() {
+        let signature = a_block.signature();
+        let b_index = b_blocks
+            .iter()
+            .enumerate()
+            .position(|(index, block)| !matched[index] && block.signature() == signature);
+        match b_index {
+            Some(b_index) => {
+                matched[b_index] = true;
+                if differs(
+                    a_block,
+                    a.extra_data().block_bytes(a_index),
+                    &b_blocks[b_index],
+                    b.extra_data().block_bytes(b_index),
+                ) {
+                    push(Structure::ExtraDataBlock(signature), ChangeKind::Modified);
+                }
+            }
+            None => push(Structure::ExtraDataBlock(signature), ChangeKind::Removed),
+        }
+    }
+    for (block, _) in b_blocks
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !*matched)
+    {
+        push(
+            Structure::ExtraDataBlock(block.signature()),
+            ChangeKind::Added,
+        );
+    }
+
+    changes
+}
+
+/// serializes `value`, or returns `None` if it cannot be written
+fn to_bytes<T: BinWrite>(value: &T, args: T::Args<'_>) -> Option<Vec<u8>> {
+    let mut cursor = Cursor::new(Vec::new());
+    let result: BinResult<()> = value.write_le_args(&mut cursor, args);
+    result.ok().map(|_| cursor.into_inner())
+}
+
+/// compares two structures by their bytes, or by their parsed fields if one
+/// of them cannot be serialized
+fn differs<T: Debug>(a: &T, a_bytes: Option<Vec<u8>>, b: &T, b_bytes: Option<Vec<u8>>) -> bool {
+    match (a_bytes, b_bytes) {
+        (Some(a_bytes), Some(b_bytes)) => a_bytes != b_bytes,
+        _ => format!("{a:?}") != format!("{b:?}"),
+    }
+}
+
+/// a structure which may be absent, together with its bytes
+type Serialized<'a, T> = Option<(&'a T, Option<Vec<u8>>)>;
+
+/// compares an optional structure of two links, given together with its
+/// bytes
+fn compare_optional<T: Debug>([a, b]: [Serialized<'_, T>; 2]) -> Option<ChangeKind> {
+    match (a, b) {
+        (None, None) => None,
+        (None, Some(_)) => Some(ChangeKind::Added),
+        (Some(_), None) => Some(ChangeKind::Removed),
+        (Some((a, a_bytes)), Some((b, b_bytes))) => {
+            differs(a, a_bytes, b, b_bytes).then_some(ChangeKind::Modified)
+        }
+    }
+}
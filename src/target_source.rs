@@ -0,0 +1,32 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// A source from which the target of a shell link can be resolved, see
+/// [`ShellLink::resolution_trace`](crate::ShellLink::resolution_trace). The
+/// variants are listed in the order in which they are tried by
+/// [`ShellLink::resolve_target`](crate::ShellLink::resolve_target).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum TargetSource {
+    /// the [`LinkInfo`](crate::LinkInfo) structure
+    LinkInfo,
+
+    /// the relative path, appended to the working directory
+    RelativePath,
+
+    /// the path of the [`LinkTargetIdList`](crate::LinkTargetIdList)
+    LinkTargetIdList,
+
+    /// the path of the IDList of the
+    /// [`VistaAndAboveIdListDataBlock`](crate::extradata::vista_and_above_id_list_data::VistaAndAboveIdListDataBlock)
+    VistaAndAboveIdList,
+
+    /// the shell namespace path of the IDList, see
+    /// [`IdList::to_shell_path`](crate::IdList::to_shell_path)
+    ShellNamespace,
+
+    /// the path of the
+    /// [`EnvironmentVariableDataBlock`](crate::extradata::environment_variable_data::EnvironmentVariableDataBlock),
+    /// see [`ShellLink::environment_target`](crate::ShellLink::environment_target)
+    EnvironmentVariables,
+}
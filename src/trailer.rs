@@ -0,0 +1,23 @@
+/// A parser for the bytes which follow the ExtraData of a shell link, like
+/// a custom trailer appended by some tool. Parsers can be passed to
+/// [`ShellLink::with_trailer_parser`](crate::ShellLink::with_trailer_parser).
+/// Closures taking the bytes of the trailer implement this trait as well.
+pub trait TrailerParser {
+    /// the type the trailer is interpreted as
+    type Output;
+
+    /// interprets `trailer`, or returns `None` if this parser does not know
+    /// the trailer
+    fn parse(&self, trailer: &[u8]) -> Option<Self::Output>;
+}
+
+impl<T, F> TrailerParser for F
+where
+    F: Fn(&[u8]) -> Option<T>,
+{
+    type Output = T;
+
+    fn parse(&self, trailer: &[u8]) -> Option<T> {
+        self(trailer)
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use thiserror::Error;
+
+/// A problem which has been found by
+/// [`ShellLink::validate`](crate::ShellLink::validate). Warnings do not
+/// prevent a shell link from being read, but they indicate that it has been
+/// corrupted or manipulated.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[non_exhaustive]
+pub enum Warning {
+    /// the CommonNetworkRelativeLinkSize field of the
+    /// [`CommonNetworkRelativeLink`](crate::linkinfo::CommonNetworkRelativeLink)
+    /// differs from the size of the fields which are present
+    #[error(
+        "The CommonNetworkRelativeLink declares a size of {declared} bytes, \
+        but its fields take {actual} bytes"
+    )]
+    CommonNetworkRelativeLinkSizeMismatch {
+        /// the value of the CommonNetworkRelativeLinkSize field
+        declared: u32,
+        /// the number of bytes of the fields which are present
+        actual: u32,
+    },
+
+    /// the offset of the
+    /// [`SpecialFolderDataBlock`](crate::extradata::special_folder_data::SpecialFolderDataBlock)
+    /// does not point to the start of an ItemID of the IDList
+    #[error(
+        "The SpecialFolderDataBlock points to offset {offset}, which is no ItemID of the IDList"
+    )]
+    SpecialFolderOffsetMismatch {
+        /// the value of the Offset field
+        offset: u32,
+    },
+
+    /// the bytes following the [`StringData`](crate::StringData) do not
+    /// start an ExtraData block. This indicates that the CountCharacters
+    /// fields of the StringData do not match its strings, e.g. because the
+    /// strings have been stored out of order or have been corrupted.
+    #[error(
+        "The StringData ends at offset {offset}, which is not the start of an ExtraData block"
+    )]
+    StringDataSizeMismatch {
+        /// the position in the input at which the StringData ends
+        offset: u64,
+    },
+
+    /// the HasLinkInfo flag is set, but the LinkInfo consists of nothing but
+    /// its size field. The flag has been cleared while reading.
+    #[error("The LinkInfo at offset {offset} is empty, its LinkInfoSize is {size}")]
+    EmptyLinkInfo {
+        /// the value of the LinkInfoSize field
+        size: u32,
+        /// the position in the input at which the LinkInfo starts
+        offset: u64,
+    },
+}
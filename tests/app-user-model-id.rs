@@ -0,0 +1,40 @@
+use lnk::{encoding::WINDOWS_1252, ShellLink};
+
+use std::fs;
+
+const TEST_FILE_NAME: &str = "temp-app-user-model-id.lnk";
+
+#[test]
+fn save_app_user_model_id() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::default();
+    shortcut.set_name(Some("Editor".to_string()));
+    shortcut.set_app_user_model_id("Contoso.Editor");
+    assert_eq!(
+        shortcut.app_user_model_id().as_deref(),
+        Some("Contoso.Editor")
+    );
+
+    shortcut.save(TEST_FILE_NAME).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+
+    let mut shortcut = shortcut.unwrap();
+    assert_eq!(
+        shortcut.app_user_model_id().as_deref(),
+        Some("Contoso.Editor")
+    );
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Editor".to_string())
+    );
+
+    // replacing the id keeps a single property store
+    shortcut.set_app_user_model_id("Contoso.Editor.2");
+    assert_eq!(
+        shortcut.app_user_model_id().as_deref(),
+        Some("Contoso.Editor.2")
+    );
+    assert_eq!(shortcut.extra_data().blocks().len(), 1);
+}
@@ -0,0 +1,20 @@
+use lnk::{encoding::WINDOWS_1252, FileTime, ShellLink};
+
+#[test]
+fn test_canonical_bytes_ignore_timestamps() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    let mut other = shortcut.clone();
+    other.header_mut().set_creation_time(FileTime::default());
+    assert_eq!(
+        shortcut.canonical_bytes().unwrap(),
+        other.canonical_bytes().unwrap()
+    );
+
+    other.set_name(Some("another name".to_string()));
+    assert_ne!(
+        shortcut.canonical_bytes().unwrap(),
+        other.canonical_bytes().unwrap()
+    );
+}
@@ -1,13 +1,18 @@
-use lnk::{encoding::WINDOWS_1252, StringEncoding};
+use lnk::{
+    encoding::WINDOWS_1252, HotkeyFlags, HotkeyKey, HotkeyModifiers, LinkFlags, StringEncoding,
+};
 use log::info;
 
 use std::fs;
 
-const TEST_FILE_NAME: &'static str = "temp.lnk";
+const TEST_FILE_NAME: &str = "temp.lnk";
+const ACCENTED_FILE_NAME: &str = "temp-accented.lnk";
+const RELATIVE_ICON_FILE_NAME: &str = "temp-relative-icon.lnk";
+const HOTKEY_FILE_NAME: &str = "temp-hotkey.lnk";
 
 #[test]
 fn create_read_blank() {
-    pretty_env_logger::init();
+    let _ = pretty_env_logger::try_init();
 
     for encoding in &[
         StringEncoding::Unicode,
@@ -33,3 +38,134 @@ fn create_read_blank() {
     info!("Cleaning up...");
     fs::remove_file(TEST_FILE_NAME).expect("delete shortcut");
 }
+
+#[test]
+fn create_read_accented() {
+    let _ = pretty_env_logger::try_init();
+
+    for encoding in &[
+        StringEncoding::Unicode,
+        StringEncoding::CodePage(WINDOWS_1252),
+    ] {
+        // the strings following the name are only read correctly if the
+        // length of the name is counted in encoded characters
+        let mut shortcut = lnk::ShellLink::default().with_encoding(encoding);
+        shortcut.set_name(Some("Café Müller".to_string()));
+        shortcut.set_arguments(Some("--größe=3".to_string()));
+        shortcut
+            .save(ACCENTED_FILE_NAME)
+            .expect("Failed to save shortcut!");
+
+        let shortcut = lnk::ShellLink::open(ACCENTED_FILE_NAME, encoding.encoding()).unwrap();
+        assert_eq!(
+            shortcut.string_data().name_string(),
+            &Some("Café Müller".to_string())
+        );
+        assert_eq!(
+            shortcut.string_data().command_line_arguments(),
+            &Some("--größe=3".to_string())
+        );
+    }
+
+    fs::remove_file(ACCENTED_FILE_NAME).expect("delete shortcut");
+}
+
+#[test]
+fn create_read_relative_icon() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = lnk::ShellLink::default();
+    shortcut.set_relative_icon(".\\app.ico", 0);
+    shortcut
+        .save(RELATIVE_ICON_FILE_NAME)
+        .expect("Failed to save shortcut!");
+
+    let shortcut = lnk::ShellLink::open(RELATIVE_ICON_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_ICON_LOCATION));
+    assert_eq!(
+        shortcut.string_data().icon_location(),
+        &Some(".\\app.ico".to_string())
+    );
+    assert_eq!(*shortcut.header().icon_index(), 0);
+
+    fs::remove_file(RELATIVE_ICON_FILE_NAME).expect("delete shortcut");
+}
+
+#[test]
+fn create_read_hotkey() {
+    let _ = pretty_env_logger::try_init();
+
+    // F13 and VK_VOLUME_MUTE, which is not modeled by HotkeyKey
+    for key in [HotkeyKey::F13, HotkeyKey::Raw(0xad)] {
+        let hotkey = HotkeyFlags::new(key, HotkeyModifiers::HOTKEYF_CONTROL);
+        let mut shortcut = lnk::ShellLink::default();
+        shortcut.header_mut().set_hotkey(hotkey);
+        shortcut
+            .save(HOTKEY_FILE_NAME)
+            .expect("Failed to save shortcut!");
+
+        let shortcut = lnk::ShellLink::open(HOTKEY_FILE_NAME, WINDOWS_1252).unwrap();
+        assert_eq!(*shortcut.header().hotkey(), hotkey);
+    }
+
+    fs::remove_file(HOTKEY_FILE_NAME).expect("delete shortcut");
+}
+
+#[test]
+fn to_bytes_from_bytes() {
+    let _ = pretty_env_logger::try_init();
+
+    for encoding in &[
+        StringEncoding::Unicode,
+        StringEncoding::CodePage(WINDOWS_1252),
+    ] {
+        let mut shortcut = lnk::ShellLink::default().with_encoding(encoding);
+        shortcut.set_name(Some("Café Müller".to_string()));
+        let bytes = shortcut.to_bytes().unwrap();
+
+        let shortcut = lnk::ShellLink::from_bytes(&bytes, encoding.encoding()).unwrap();
+        assert_eq!(
+            shortcut.string_data().name_string(),
+            &Some("Café Müller".to_string())
+        );
+    }
+}
+
+#[test]
+fn empty_link_info_round_trip() {
+    let _ = pretty_env_logger::try_init();
+
+    // a LinkInfo which consists of nothing but a LinkInfoSize of zero
+    let mut bytes = fs::read("tests/data/test.lnk").unwrap()[..0x4c].to_vec();
+    let link_flags = LinkFlags::HAS_LINK_INFO | LinkFlags::HAS_NAME | LinkFlags::IS_UNICODE;
+    bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&4u16.to_le_bytes());
+    bytes.extend("name".encode_utf16().flat_map(|c| c.to_le_bytes()));
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = lnk::ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert!(!shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_LINK_INFO));
+    assert_eq!(
+        shortcut.validate(),
+        [lnk::Warning::EmptyLinkInfo {
+            size: 0,
+            offset: 0x4c
+        }]
+    );
+
+    let saved = shortcut.to_bytes().unwrap();
+    let shortcut = lnk::ShellLink::from_bytes(&saved, WINDOWS_1252).unwrap();
+    assert!(shortcut.link_info().is_none());
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("name".to_string())
+    );
+    assert!(shortcut.validate().is_empty());
+}
@@ -0,0 +1,135 @@
+use lnk::{
+    encoding::WINDOWS_1252, extradata::ExtraDataBlock, structural_diff, ShellLink, Structure,
+};
+
+use std::fs;
+
+const TEST_FILE_NAME: &str = "temp-extra-data-fidelity.lnk";
+
+/// an ExtraData block with a signature which is not known to the crate
+const UNKNOWN_BLOCK: [u8; 16] = [
+    0x10, 0, 0, 0, 0xff, 0, 0, 0xa0, 0xde, 0xad, 0xbe, 0xef, 1, 2, 3, 4,
+];
+
+/// a custom trailer, which some tool has appended after the ExtraData
+const TRAILER: &[u8] = b"TRLR\x08\x00\x00\x00comment!";
+
+/// a ConsoleFEDataBlock using code page 850
+const CONSOLE_FE_BLOCK: [u8; 12] = [0x0c, 0, 0, 0, 0x04, 0, 0, 0xa0, 0x52, 0x03, 0, 0];
+
+#[test]
+fn unknown_block_survives_save() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::default();
+    shortcut.set_name(Some("before".to_string()));
+    let mut bytes = shortcut.canonical_bytes().unwrap();
+
+    // replace the terminal block by our own ExtraData
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&UNKNOWN_BLOCK);
+    bytes.extend_from_slice(&CONSOLE_FE_BLOCK);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    fs::write(TEST_FILE_NAME, &bytes).unwrap();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(matches!(
+        &shortcut.extra_data().blocks()[0],
+        ExtraDataBlock::Unknown { signature: 0xa00000ff, data } if data == &UNKNOWN_BLOCK[8..]
+    ));
+
+    shortcut.set_name(Some("after".to_string()));
+    shortcut.save(TEST_FILE_NAME).unwrap();
+    let saved = fs::read(TEST_FILE_NAME);
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+
+    let mut expected = UNKNOWN_BLOCK.to_vec();
+    expected.extend_from_slice(&CONSOLE_FE_BLOCK);
+    expected.extend_from_slice(&0u32.to_le_bytes());
+    assert!(saved.unwrap().ends_with(&expected));
+
+    let shortcut = shortcut.unwrap();
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("after".to_string())
+    );
+    assert_eq!(shortcut.extra_data().blocks().len(), 2);
+}
+
+#[test]
+fn recognized_blocks_survive_save() {
+    let _ = pretty_env_logger::try_init();
+
+    const ROUND_TRIP_FILE_NAME: &str = "temp-extra-data-round-trip.lnk";
+
+    for source in ["tests/data/test.lnk", "tests/data/Windows PowerShell.lnk"] {
+        let original = ShellLink::open(source, WINDOWS_1252).unwrap();
+
+        // copying the blocks into another link discards the bytes they have
+        // been read from, so that they are serialized from their fields
+        let mut shortcut = ShellLink::default();
+        shortcut.merge_extra_data_from(&original, false);
+        shortcut.save(ROUND_TRIP_FILE_NAME).unwrap();
+        let saved = ShellLink::open(ROUND_TRIP_FILE_NAME, WINDOWS_1252);
+        fs::remove_file(ROUND_TRIP_FILE_NAME).unwrap();
+        let saved = saved.unwrap();
+
+        let tracker = original.extra_data().tracker().unwrap();
+        let saved_tracker = saved.extra_data().tracker().unwrap();
+        assert_eq!(saved_tracker.machine_id(), tracker.machine_id());
+        assert_eq!(saved_tracker.droid(), tracker.droid());
+        assert_eq!(saved_tracker.droid_birth(), tracker.droid_birth());
+
+        assert_eq!(
+            saved.extra_data().blocks().len(),
+            original.extra_data().blocks().len()
+        );
+        assert!(structural_diff(&original, &saved)
+            .iter()
+            .all(|change| !matches!(change.structure(), Structure::ExtraDataBlock(_))));
+    }
+}
+
+#[test]
+fn trailer_survives_save() {
+    let _ = pretty_env_logger::try_init();
+
+    const TRAILER_FILE_NAME: &str = "temp-trailer.lnk";
+
+    let mut bytes = fs::read("tests/data/test.lnk").unwrap();
+    bytes.extend_from_slice(TRAILER);
+    fs::write(TRAILER_FILE_NAME, &bytes).unwrap();
+
+    let mut shortcut = ShellLink::open(TRAILER_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.trailer(), TRAILER);
+
+    shortcut.set_name(Some("with trailer".to_string()));
+    shortcut.save(TRAILER_FILE_NAME).unwrap();
+    let saved = fs::read(TRAILER_FILE_NAME);
+    let shortcut = ShellLink::open(TRAILER_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TRAILER_FILE_NAME).unwrap();
+
+    let mut expected = 0u32.to_le_bytes().to_vec();
+    expected.extend_from_slice(TRAILER);
+    assert!(saved.unwrap().ends_with(&expected));
+    assert_eq!(shortcut.unwrap().trailer(), TRAILER);
+}
+
+#[test]
+fn trailer_with_leading_zeros_survives_save() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut bytes = fs::read("tests/data/test.lnk").unwrap();
+    let trailer = [&[0; 4][..], TRAILER].concat();
+    bytes.extend_from_slice(&trailer);
+
+    let shortcut = ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.trailer(), trailer);
+
+    // the zero bytes are written back in front of the trailer
+    let saved = shortcut.to_bytes().unwrap();
+    assert!(saved.ends_with(&[&0u32.to_le_bytes()[..], &trailer].concat()));
+    let shortcut = ShellLink::from_bytes(&saved, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.trailer(), trailer);
+}
@@ -0,0 +1,201 @@
+use std::{fs, io::Cursor};
+
+use binrw::BinReaderExt;
+use lnk::{
+    encoding::{WINDOWS_1251, WINDOWS_1252},
+    Error, LinkFlags, LinkInfo, ShellLink, StringEncoding,
+};
+
+const TEST_FILE_NAME: &str = "temp-link-info-encoding.lnk";
+
+/// creates a link to a file on a fixed local drive, with a LinkInfo
+/// structure which uses the code page only
+fn link_to(local_base_path: &str) -> ShellLink {
+    link_in_code_page(local_base_path, WINDOWS_1252)
+}
+
+/// creates a link like [`link_to`], whose LinkInfo uses `codepage`
+fn link_in_code_page(local_base_path: &str, codepage: lnk::Encoding) -> ShellLink {
+    let local_base_path = codepage.encode(local_base_path).0;
+    let mut volume_id = Vec::new();
+    volume_id.extend_from_slice(&0x11u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x3u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x1234_5678u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x10u32.to_le_bytes());
+    volume_id.push(0);
+
+    let header_size = 0x1cu32;
+    let local_base_path_offset = header_size + volume_id.len() as u32;
+    let suffix_offset = local_base_path_offset + local_base_path.len() as u32 + 1;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(suffix_offset + 1).to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&0x1u32.to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&local_base_path_offset.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&suffix_offset.to_le_bytes());
+    bytes.extend_from_slice(&volume_id);
+    bytes.extend_from_slice(&local_base_path);
+    bytes.extend_from_slice(&[0, 0]);
+
+    let info: LinkInfo = Cursor::new(bytes).read_le_args((codepage,)).unwrap();
+    let mut shortcut = ShellLink::default();
+    shortcut
+        .header_mut()
+        .update_link_flags(LinkFlags::HAS_LINK_INFO, true);
+    *shortcut.link_info_mut() = Some(info);
+    shortcut
+}
+
+fn save_and_open(shortcut: &ShellLink) -> ShellLink {
+    shortcut.save(TEST_FILE_NAME).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+    shortcut.unwrap()
+}
+
+/// serializes `shortcut` and parses it again, using `codepage` as system
+/// default code page
+fn round_trip(shortcut: &ShellLink, codepage: lnk::Encoding) -> ShellLink {
+    ShellLink::from_bytes(&shortcut.to_bytes().unwrap(), codepage).unwrap()
+}
+
+#[test]
+fn unicode_string_data_with_ansi_link_info() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = link_to(r"C:\Windows\notepad.exe");
+    shortcut.set_string_data_encoding(&StringEncoding::Unicode);
+    shortcut.set_link_info_encoding(&StringEncoding::CodePage(WINDOWS_1252));
+    shortcut.set_name(Some("Notizblock \u{270e}".to_string()));
+
+    let shortcut = save_and_open(&shortcut);
+    assert!(shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::IS_UNICODE));
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Notizblock \u{270e}".to_string())
+    );
+
+    let info = shortcut.link_info().as_ref().unwrap();
+    assert_eq!(*info.link_info_header_size(), 0x1c);
+    assert_eq!(info.local_base_path(), Some(r"C:\Windows\notepad.exe"));
+    assert_eq!(info.local_base_path_unicode(), &None);
+    assert_eq!(
+        shortcut.link_target(),
+        Some(r"C:\Windows\notepad.exe".to_string())
+    );
+}
+
+#[test]
+fn ansi_string_data_with_unicode_link_info() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = link_to(r"C:\Windows\notepad.exe");
+    shortcut.set_string_data_encoding(&StringEncoding::CodePage(WINDOWS_1252));
+    shortcut.set_link_info_encoding(&StringEncoding::Unicode);
+    shortcut.set_name(Some("Notizblock".to_string()));
+
+    let shortcut = save_and_open(&shortcut);
+    assert!(!shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::IS_UNICODE));
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Notizblock".to_string())
+    );
+
+    let info = shortcut.link_info().as_ref().unwrap();
+    assert_eq!(*info.link_info_header_size(), 0x24);
+    assert_eq!(info.local_base_path(), Some(r"C:\Windows\notepad.exe"));
+    assert_eq!(
+        info.local_base_path_unicode(),
+        &Some(r"C:\Windows\notepad.exe".to_string())
+    );
+    assert_eq!(
+        info.volume_id().as_ref().unwrap().drive_serial_number(),
+        &0x1234_5678
+    );
+}
+
+#[test]
+fn save_keeps_link_target() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    let saved = save_and_open(&shortcut);
+    assert!(shortcut.link_target().is_some());
+    assert_eq!(saved.link_target(), shortcut.link_target());
+
+    let original = shortcut.link_info().as_ref().unwrap();
+    let info = saved.link_info().as_ref().unwrap();
+    assert_eq!(info.local_base_path(), original.local_base_path());
+    assert_eq!(info.common_path_suffix(), original.common_path_suffix());
+}
+
+#[test]
+fn unicode_link_info_uses_system_code_page() {
+    let _ = pretty_env_logger::try_init();
+
+    let path = r"C:\Users\Иван\notes.txt";
+    let shortcut = link_in_code_page(path, WINDOWS_1251);
+    let mut shortcut = round_trip(&shortcut, WINDOWS_1251);
+    shortcut.set_string_data_encoding(&StringEncoding::Unicode);
+    shortcut.set_name(Some("Заметки".to_string()));
+
+    let shortcut = round_trip(&shortcut, WINDOWS_1251);
+    let info = shortcut.link_info().as_ref().unwrap();
+    assert_eq!(*info.link_info_header_size(), 0x24);
+    assert_eq!(info.local_base_path(), Some(path));
+    assert_eq!(info.local_base_path_unicode(), &Some(path.to_string()));
+}
+
+#[test]
+fn unrepresentable_characters_are_replaced() {
+    let _ = pretty_env_logger::try_init();
+
+    let path = r"C:\Users\Иван\notes.txt";
+    let mut shortcut = round_trip(&link_in_code_page(path, WINDOWS_1251), WINDOWS_1251);
+    shortcut.set_string_data_encoding(&StringEncoding::Unicode);
+    let shortcut = round_trip(&shortcut, WINDOWS_1251);
+
+    // the Unicode path cannot be represented in Windows-1252
+    let shortcut = round_trip(&round_trip(&shortcut, WINDOWS_1252), WINDOWS_1252);
+    let info = shortcut.link_info().as_ref().unwrap();
+    assert_eq!(info.local_base_path(), Some(r"C:\Users\????\notes.txt"));
+    assert_eq!(info.local_base_path_unicode(), &Some(path.to_string()));
+}
+
+#[test]
+fn reencode_checks_link_info() {
+    let _ = pretty_env_logger::try_init();
+
+    let path = r"C:\Users\Иван\notes.txt";
+    let mut shortcut = round_trip(&link_in_code_page(path, WINDOWS_1251), WINDOWS_1251);
+    shortcut.set_string_data_encoding(&StringEncoding::Unicode);
+    let mut shortcut = round_trip(&shortcut, WINDOWS_1251);
+
+    // the StringData is empty, but LocalBasePathUnicode cannot be
+    // represented in Windows-1252
+    let result = shortcut.reencode(&StringEncoding::CodePage(WINDOWS_1252));
+    assert!(matches!(
+        result,
+        Err(Error::UnrepresentableString { string, .. }) if string == path
+    ));
+    assert!(shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::IS_UNICODE));
+
+    shortcut
+        .reencode(&StringEncoding::CodePage(WINDOWS_1251))
+        .unwrap();
+    let shortcut = round_trip(&shortcut, WINDOWS_1251);
+    let info = shortcut.link_info().as_ref().unwrap();
+    assert_eq!(info.local_base_path(), Some(path));
+}
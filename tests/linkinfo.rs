@@ -0,0 +1,347 @@
+use std::io::Cursor;
+
+use binrw::BinReaderExt;
+use lnk::{
+    encoding::WINDOWS_1252,
+    linkinfo::{CommonNetworkRelativeLinkFlags, NetworkProviderType},
+    Error, LinkFlags, LinkInfo, OpenOptions, ShellLink, Warning,
+};
+
+/// creates the bytes of a LinkInfo structure which points to a network
+/// share, optionally mapped to a drive letter
+fn network_link_info(net_name: &str, device_name: Option<&str>, suffix: &str) -> Vec<u8> {
+    let mut cnrl_data = Vec::new();
+    cnrl_data.extend_from_slice(net_name.as_bytes());
+    cnrl_data.push(0);
+    let device_name_offset = if let Some(device_name) = device_name {
+        let offset = 0x14 + cnrl_data.len() as u32;
+        cnrl_data.extend_from_slice(device_name.as_bytes());
+        cnrl_data.push(0);
+        offset
+    } else {
+        0
+    };
+    let flags: u32 = if device_name.is_some() { 0x3 } else { 0x2 };
+
+    let mut cnrl = Vec::new();
+    cnrl.extend_from_slice(&(0x14 + cnrl_data.len() as u32).to_le_bytes());
+    cnrl.extend_from_slice(&flags.to_le_bytes());
+    cnrl.extend_from_slice(&0x14u32.to_le_bytes());
+    cnrl.extend_from_slice(&device_name_offset.to_le_bytes());
+    cnrl.extend_from_slice(&0x0002_0000u32.to_le_bytes());
+    cnrl.extend_from_slice(&cnrl_data);
+
+    let header_size = 0x1cu32;
+    let suffix_offset = header_size + cnrl.len() as u32;
+    let size = suffix_offset + suffix.len() as u32 + 1;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&0x2u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&suffix_offset.to_le_bytes());
+    bytes.extend_from_slice(&cnrl);
+    bytes.extend_from_slice(suffix.as_bytes());
+    bytes.push(0);
+    bytes
+}
+
+/// creates the bytes of a LinkInfo structure which points to a file on a
+/// fixed local drive
+fn local_link_info(local_base_path: &str) -> Vec<u8> {
+    let mut volume_id = Vec::new();
+    volume_id.extend_from_slice(&0x11u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x3u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x1234_5678u32.to_le_bytes());
+    volume_id.extend_from_slice(&0x10u32.to_le_bytes());
+    volume_id.push(0);
+
+    let header_size = 0x1cu32;
+    let local_base_path_offset = header_size + volume_id.len() as u32;
+    let suffix_offset = local_base_path_offset + local_base_path.len() as u32 + 1;
+    let size = suffix_offset + 1;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&0x1u32.to_le_bytes());
+    bytes.extend_from_slice(&header_size.to_le_bytes());
+    bytes.extend_from_slice(&local_base_path_offset.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&suffix_offset.to_le_bytes());
+    bytes.extend_from_slice(&volume_id);
+    bytes.extend_from_slice(local_base_path.as_bytes());
+    bytes.push(0);
+    bytes.push(0);
+    bytes
+}
+
+fn link_with_info(bytes: Vec<u8>) -> ShellLink {
+    let info: LinkInfo = Cursor::new(bytes).read_le_args((WINDOWS_1252,)).unwrap();
+    let mut shortcut = ShellLink::default();
+    shortcut
+        .header_mut()
+        .update_link_flags(LinkFlags::HAS_LINK_INFO, true);
+    *shortcut.link_info_mut() = Some(info);
+    shortcut
+}
+
+#[test]
+fn test_mapped_drive_target() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(network_link_info(
+        r"\\nas\share",
+        Some("Z:"),
+        r"folder\file.txt",
+    ));
+    // the mapped device is preferred over the UNC share
+    assert_eq!(
+        shortcut.link_target(),
+        Some(r"Z:\folder\file.txt".to_string())
+    );
+    assert_eq!(
+        shortcut.mapped_drive_target(),
+        Some(r"Z:\folder\file.txt".to_string())
+    );
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", None, "file.txt"));
+    assert_eq!(
+        shortcut.link_target(),
+        Some(r"\\nas\share\file.txt".to_string())
+    );
+    assert_eq!(shortcut.mapped_drive_target(), None);
+}
+
+#[test]
+fn test_target_filename() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(local_link_info(r"C:\Windows\System32\notepad.exe"));
+    assert_eq!(shortcut.target_filename(), Some("notepad.exe".to_string()));
+
+    let shortcut = link_with_info(local_link_info(r"C:\Windows\System32\"));
+    assert_eq!(shortcut.target_filename(), None);
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", None, "file.txt"));
+    assert_eq!(shortcut.target_filename(), Some("file.txt".to_string()));
+
+    assert_eq!(ShellLink::default().target_filename(), None);
+}
+
+#[test]
+fn test_empty_link_info() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut bytes = std::fs::read("tests/data/test.lnk").unwrap()[..0x4c].to_vec();
+    let link_flags = LinkFlags::HAS_LINK_INFO | LinkFlags::HAS_NAME | LinkFlags::IS_UNICODE;
+    bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&4u16.to_le_bytes());
+    bytes.extend("name".encode_utf16().flat_map(|c| c.to_le_bytes()));
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert!(shortcut.link_info().is_none());
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("name".to_string())
+    );
+}
+
+#[test]
+fn test_target_drive() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(local_link_info(r"c:\Windows\System32\notepad.exe"));
+    assert_eq!(shortcut.target_drive(), Some('C'));
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", Some("Z:"), "file.txt"));
+    assert_eq!(shortcut.target_drive(), Some('Z'));
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", None, "file.txt"));
+    assert_eq!(shortcut.target_drive(), None);
+}
+
+#[test]
+fn test_common_network_relative_link_flags() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", Some("Z:"), "file.txt"));
+    let cnrl = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .common_network_relative_link()
+        .as_ref()
+        .unwrap();
+    assert!(cnrl.has_valid_device());
+    assert!(cnrl.has_valid_net_type());
+    assert_eq!(
+        cnrl.flags(),
+        CommonNetworkRelativeLinkFlags::VALID_DEVICE
+            | CommonNetworkRelativeLinkFlags::VALID_NET_TYPE
+    );
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", None, "file.txt"));
+    let cnrl = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .common_network_relative_link()
+        .as_ref()
+        .unwrap();
+    assert!(!cnrl.has_valid_device());
+    assert!(cnrl.has_valid_net_type());
+}
+
+#[test]
+fn test_common_network_relative_link_names() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", Some("Z:"), "file.txt"));
+    let cnrl = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .common_network_relative_link()
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        cnrl.network_provider_type(),
+        Some(&NetworkProviderType::Smb)
+    );
+    assert_eq!(cnrl.net_name(), r"\\nas\share");
+    assert_eq!(cnrl.device_name(), Some("Z:"));
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", None, "file.txt"));
+    let cnrl = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .common_network_relative_link()
+        .as_ref()
+        .unwrap();
+    assert_eq!(cnrl.device_name(), None);
+}
+
+#[test]
+fn test_implausible_link_info_size() {
+    let _ = pretty_env_logger::try_init();
+
+    // the LinkInfo region contains the start of a StringData structure
+    // instead, whose first bytes are read as LinkInfoSize
+    let header = std::fs::read("tests/data/test.lnk").unwrap()[..0x4c].to_vec();
+    let open_with_link_info = |link_info: &[u8]| {
+        let mut bytes = header.clone();
+        let link_flags = LinkFlags::HAS_LINK_INFO | LinkFlags::HAS_NAME | LinkFlags::IS_UNICODE;
+        bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+        bytes.extend_from_slice(link_info);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+
+        ShellLink::read(&mut Cursor::new(bytes), WINDOWS_1252)
+    };
+
+    let mut name = 4u16.to_le_bytes().to_vec();
+    name.extend("name".encode_utf16().flat_map(|c| c.to_le_bytes()));
+    let error = open_with_link_info(&name).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidLinkInfoSize {
+            size: 0x006e_0004,
+            ..
+        }
+    ));
+    assert_eq!(error.offset(), Some(0x4c));
+
+    // a LinkInfoSize which is too small to hold the LinkInfo header
+    let mut link_info = vec![0; 0x10];
+    link_info[0] = 0x10;
+    let error = open_with_link_info(&link_info).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidLinkInfoSize { size: 0x10, .. }
+    ));
+    assert_eq!(error.offset(), Some(0x4c));
+}
+
+#[test]
+fn test_file_relative_link_info_offsets() {
+    let _ = pretty_env_logger::try_init();
+
+    // a buggy creator wrote the offsets relative to the start of the file,
+    // which is 0x4c bytes in front of the LinkInfo
+    let mut link_info = local_link_info(r"C:\Users\Public\file.txt");
+    for field in link_info[12..28].chunks_exact_mut(4) {
+        let offset = u32::from_le_bytes(field.try_into().unwrap());
+        if offset != 0 {
+            field.copy_from_slice(&(offset + 0x4c).to_le_bytes());
+        }
+    }
+
+    let mut bytes = std::fs::read("tests/data/test.lnk").unwrap()[..0x4c].to_vec();
+    bytes[0x14..0x18].copy_from_slice(&LinkFlags::HAS_LINK_INFO.bits().to_le_bytes());
+    bytes.extend_from_slice(&link_info);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let strict = ShellLink::read(&mut Cursor::new(&bytes), WINDOWS_1252);
+    let lenient = ShellLink::read_with_options(
+        &mut Cursor::new(bytes),
+        WINDOWS_1252,
+        OpenOptions::default().with_detect_file_relative_offsets(true),
+    );
+
+    assert!(strict.is_err());
+    assert_eq!(
+        lenient.unwrap().link_target(),
+        Some(r"C:\Users\Public\file.txt".to_string())
+    );
+}
+
+#[test]
+fn test_common_network_relative_link_size_warning() {
+    let _ = pretty_env_logger::try_init();
+
+    let bytes = network_link_info(r"\\nas\share", Some("Z:"), "file.txt");
+    let shortcut = link_with_info(bytes.clone());
+    assert!(shortcut.validate().is_empty());
+
+    // the CommonNetworkRelativeLink follows the LinkInfo header
+    let mut bytes = bytes;
+    let size = u32::from_le_bytes(bytes[0x1c..0x20].try_into().unwrap());
+    bytes[0x1c..0x20].copy_from_slice(&(size + 8).to_le_bytes());
+    let shortcut = link_with_info(bytes);
+    assert_eq!(
+        shortcut.validate(),
+        vec![Warning::CommonNetworkRelativeLinkSizeMismatch {
+            declared: size + 8,
+            actual: size,
+        }]
+    );
+}
+
+#[test]
+fn test_is_webdav_target() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = link_with_info(network_link_info(r"\\host@SSL\path", None, "file.txt"));
+    assert!(shortcut.is_webdav_target());
+
+    let shortcut = link_with_info(network_link_info(r"\\host@8080\path", None, "file.txt"));
+    assert!(shortcut.is_webdav_target());
+
+    let shortcut = link_with_info(network_link_info(r"\\nas\share", Some("Z:"), "file.txt"));
+    assert!(!shortcut.is_webdav_target());
+
+    // the network provider of the CommonNetworkRelativeLink is WebDAV
+    let mut bytes = network_link_info(r"\\nas\share", None, "file.txt");
+    bytes[0x2c..0x30].copy_from_slice(&0x002e_0000u32.to_le_bytes());
+    let shortcut = link_with_info(bytes);
+    assert!(shortcut.is_webdav_target());
+
+    let shortcut = link_with_info(local_link_info(r"C:\host@SSL\file.txt"));
+    assert!(!shortcut.is_webdav_target());
+}
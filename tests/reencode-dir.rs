@@ -0,0 +1,64 @@
+use lnk::{encoding::WINDOWS_1252, reencode_dir, Error, LinkFlags, ShellLink, StringEncoding};
+
+use std::fs;
+
+#[test]
+fn reencode_dir_to_unicode() {
+    let _ = pretty_env_logger::try_init();
+
+    let dir = std::env::temp_dir().join(format!("lnk-reencode-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    for name in ["a.lnk", "b.lnk"] {
+        let mut shortcut = ShellLink::default();
+        shortcut.set_string_data_encoding(&StringEncoding::CodePage(WINDOWS_1252));
+        shortcut.set_name(Some(format!("Caf\u{e9} {name}")));
+        shortcut.save(dir.join(name)).unwrap();
+    }
+    fs::write(dir.join("c.txt"), b"not a link").unwrap();
+
+    let results = reencode_dir(&dir, WINDOWS_1252, StringEncoding::Unicode);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+    for name in ["a.lnk", "b.lnk"] {
+        let shortcut = ShellLink::open(dir.join(name), WINDOWS_1252).unwrap();
+        assert!(shortcut
+            .header()
+            .link_flags()
+            .contains(LinkFlags::IS_UNICODE));
+        assert_eq!(
+            shortcut.string_data().name_string().as_deref(),
+            Some(format!("Caf\u{e9} {name}").as_str())
+        );
+    }
+    assert_eq!(fs::read(dir.join("c.txt")).unwrap(), b"not a link");
+    // the temporary files have been renamed
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reencode_dir_unrepresentable() {
+    let _ = pretty_env_logger::try_init();
+
+    let dir = std::env::temp_dir().join(format!("lnk-reencode-lossy-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut shortcut = ShellLink::default();
+    shortcut.set_string_data_encoding(&StringEncoding::Unicode);
+    shortcut.set_name(Some("\u{6587}\u{66f8}".to_string()));
+    shortcut.save(dir.join("cjk.lnk")).unwrap();
+    let original = fs::read(dir.join("cjk.lnk")).unwrap();
+
+    let results = reencode_dir(&dir, WINDOWS_1252, StringEncoding::CodePage(WINDOWS_1252));
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        results[0].1,
+        Err(Error::UnrepresentableString { .. })
+    ));
+    assert_eq!(fs::read(dir.join("cjk.lnk")).unwrap(), original);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,41 @@
+use lnk::{encoding::WINDOWS_1252, Error, LinkFlags, ShellLink};
+
+use std::fs;
+
+const TEST_FILE_NAME: &str = "temp-save-id-list.lnk";
+
+#[test]
+fn id_list_round_trip() {
+    let _ = pretty_env_logger::try_init();
+
+    let original = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    original.save(TEST_FILE_NAME).unwrap();
+    let saved = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+    let saved = saved.unwrap();
+
+    let original = original.linktarget_id_list().as_ref().unwrap();
+    let saved = saved.linktarget_id_list().as_ref().unwrap();
+    assert_eq!(saved.size, original.size);
+    assert_eq!(saved.id_list().len(), original.id_list().len());
+    for (saved, original) in saved.id_list().iter().zip(original.id_list()) {
+        assert_eq!(saved.size(), original.size());
+        assert_eq!(saved.data(), original.data());
+    }
+}
+
+#[test]
+fn missing_id_list_fails() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::default();
+    shortcut
+        .header_mut()
+        .update_link_flags(LinkFlags::HAS_LINK_TARGET_ID_LIST, true);
+    let result = shortcut.save(TEST_FILE_NAME);
+    let _ = fs::remove_file(TEST_FILE_NAME);
+    assert!(matches!(
+        result,
+        Err(Error::BinWriteError("LinkTargetIdList", _))
+    ));
+}
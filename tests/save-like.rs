@@ -0,0 +1,94 @@
+use lnk::{encoding::WINDOWS_1252, ShellLink};
+
+use std::fs;
+
+const REFERENCE_FILE_NAME: &str = "tests/data/Windows PowerShell.lnk";
+const TEST_FILE_NAME: &str = "temp-save-like.lnk";
+
+#[test]
+fn save_like_changes_only_edited_bytes() {
+    let _ = pretty_env_logger::try_init();
+
+    let reference = fs::read(REFERENCE_FILE_NAME).unwrap();
+    let mut shortcut = ShellLink::open(REFERENCE_FILE_NAME, WINDOWS_1252).unwrap();
+
+    shortcut.save_like(TEST_FILE_NAME, &reference).unwrap();
+    assert_eq!(fs::read(TEST_FILE_NAME).unwrap(), reference);
+
+    let name = shortcut.string_data().name_string().clone().unwrap();
+    let new_name = name.to_uppercase();
+    assert_eq!(name.len(), new_name.len());
+    shortcut.set_name(Some(new_name.clone()));
+    shortcut.save_like(TEST_FILE_NAME, &reference).unwrap();
+    let output = fs::read(TEST_FILE_NAME).unwrap();
+    let saved = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(saved.string_data().name_string(), &Some(new_name));
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+
+    // the name is stored as UTF-16LE, directly after its character count
+    let name_bytes: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let name_start = reference
+        .windows(name_bytes.len())
+        .position(|w| w == name_bytes)
+        .unwrap();
+    let name_region = name_start..name_start + name_bytes.len();
+
+    assert_eq!(output.len(), reference.len());
+    for (offset, (a, b)) in output.iter().zip(reference.iter()).enumerate() {
+        if a != b {
+            assert!(
+                name_region.contains(&offset),
+                "unexpected change at {offset:#x}"
+            );
+        }
+    }
+}
+
+#[test]
+fn save_like_writes_structures_missing_in_reference() {
+    let _ = pretty_env_logger::try_init();
+    const FILE_NAME: &str = "temp-save-like-missing.lnk";
+
+    // the reference has neither a LinkTargetIDList nor a LinkInfo
+    let reference = ShellLink::default().to_bytes().unwrap();
+    let shortcut = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    assert!(shortcut.link_info().is_some());
+
+    shortcut.save_like(FILE_NAME, &reference).unwrap();
+    let saved = ShellLink::open(FILE_NAME, WINDOWS_1252);
+    fs::remove_file(FILE_NAME).unwrap();
+    let saved = saved.unwrap();
+
+    assert_eq!(saved.link_target(), shortcut.link_target());
+    assert_eq!(
+        saved.linktarget_id_list().is_some(),
+        shortcut.linktarget_id_list().is_some()
+    );
+    assert_eq!(
+        saved.extra_data().block_kinds(),
+        shortcut.extra_data().block_kinds()
+    );
+}
+
+#[test]
+fn save_like_keeps_edited_extra_data() {
+    let _ = pretty_env_logger::try_init();
+    const FILE_NAME: &str = "temp-save-like-extra-data.lnk";
+
+    let reference = fs::read(REFERENCE_FILE_NAME).unwrap();
+    let mut shortcut = ShellLink::open(REFERENCE_FILE_NAME, WINDOWS_1252).unwrap();
+    shortcut.set_app_user_model_id("Contoso.Shell");
+    shortcut.set_trailer(b"trailer".to_vec());
+
+    shortcut.save_like(FILE_NAME, &reference).unwrap();
+    let saved = ShellLink::open(FILE_NAME, WINDOWS_1252);
+    fs::remove_file(FILE_NAME).unwrap();
+    let saved = saved.unwrap();
+
+    assert_eq!(saved.app_user_model_id().as_deref(), Some("Contoso.Shell"));
+    assert_eq!(saved.trailer(), b"trailer");
+    assert_eq!(
+        saved.string_data().name_string(),
+        shortcut.string_data().name_string()
+    );
+}
@@ -0,0 +1,46 @@
+use lnk::{encoding::WINDOWS_1252, structural_diff, ChangeKind, ShellLink, Structure};
+
+/// the signature of the PropertyStoreDataBlock
+const PROPERTY_STORE_PROPS: u32 = 0xa000_0009;
+
+#[test]
+fn diff_extra_data_block() {
+    let _ = pretty_env_logger::try_init();
+
+    let a = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    assert!(structural_diff(&a, &a.clone()).is_empty());
+
+    let mut a = a;
+    a.set_app_user_model_id("Contoso.Editor");
+    let mut b = a.clone();
+    b.set_app_user_model_id("Contoso.Viewer");
+
+    let changes = structural_diff(&a, &b);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(
+        changes[0].structure(),
+        Structure::ExtraDataBlock(PROPERTY_STORE_PROPS)
+    );
+    assert_eq!(changes[0].kind(), ChangeKind::Modified);
+}
+
+#[test]
+fn diff_added_and_removed() {
+    let _ = pretty_env_logger::try_init();
+
+    let a = ShellLink::open("tests/data/test.lnk", WINDOWS_1252).unwrap();
+    let mut b = a.clone();
+    b.set_app_user_model_id("Contoso.Editor");
+
+    let changes = structural_diff(&a, &b);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(
+        changes[0].structure(),
+        Structure::ExtraDataBlock(PROPERTY_STORE_PROPS)
+    );
+    assert_eq!(changes[0].kind(), ChangeKind::Added);
+
+    let changes = structural_diff(&b, &a);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind(), ChangeKind::Removed);
+}
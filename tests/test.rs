@@ -7,6 +7,14 @@ use lnk::*;
 #[allow(unused)]
 use log::{debug, error, info, trace, warn};
 
+/// returns the header of test.lnk with the given link flags. The structures
+/// of a crafted link are appended to it.
+fn crafted_header(link_flags: LinkFlags) -> Vec<u8> {
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap()[..0x4c].to_vec();
+    bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+    bytes
+}
+
 #[test]
 fn test_lnk_header() {
     let _ = pretty_env_logger::try_init();
@@ -87,3 +95,1821 @@ fn test_no_panic_reading_other_filetypes() {
     // Shouldn't have panicked by now!
     assert!(res.is_err());
 }
+
+#[test]
+fn test_refresh_target_attributes() {
+    let _ = pretty_env_logger::try_init();
+
+    let dir = std::env::temp_dir().join(format!("lnk-refresh-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("subdir")).unwrap();
+    std::fs::write(dir.join("file.txt"), b"test").unwrap();
+
+    let mut shortcut = ShellLink::default();
+    shortcut.set_working_dir(Some(dir.to_str().unwrap().to_string()));
+
+    shortcut.set_relative_path(Some(r".\file.txt".to_string()));
+    shortcut.refresh_target_attributes().unwrap();
+    assert!(!shortcut
+        .header()
+        .file_attributes()
+        .contains(FileAttributeFlags::FILE_ATTRIBUTE_DIRECTORY));
+
+    shortcut.set_relative_path(Some(r".\subdir".to_string()));
+    shortcut.refresh_target_attributes().unwrap();
+    assert!(shortcut
+        .header()
+        .file_attributes()
+        .contains(FileAttributeFlags::FILE_ATTRIBUTE_DIRECTORY));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_merge_extra_data() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let other = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    assert!(!shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_EXP_STRING));
+
+    shortcut.merge_extra_data_from(&other, true);
+
+    assert!(shortcut
+        .extra_data()
+        .blocks()
+        .iter()
+        .any(|b| matches!(b, extradata::ExtraDataBlock::EnvironmentProps(_))));
+    assert!(shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_EXP_STRING));
+
+    // the tracker block of test.lnk has been replaced, not duplicated
+    assert_eq!(
+        shortcut
+            .extra_data()
+            .blocks()
+            .iter()
+            .filter(|b| matches!(b, extradata::ExtraDataBlock::TrackerProps(_)))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_idlist_to_path() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.effective_idlist().and_then(IdList::to_path),
+        Some(r"C:\test\a.txt".to_string())
+    );
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.effective_idlist().and_then(IdList::to_path),
+        Some(r"C:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe".to_string())
+    );
+}
+
+#[test]
+fn test_vista_idlist_fallback() {
+    let _ = pretty_env_logger::try_init();
+
+    // move the LinkTargetIDList of test.lnk into a VistaAndAboveIDListDataBlock,
+    // and drop the LinkInfo and StringData, so that the IDList is the only
+    // source left
+    let original = std::fs::read(TEST_FILE_NAME).unwrap();
+    let idlist_size = usize::from(u16::from_le_bytes([original[0x4c], original[0x4d]]));
+    let idlist = &original[0x4e..0x4e + idlist_size];
+
+    let mut bytes = original[..0x4c].to_vec();
+    let link_flags =
+        LinkFlags::from_bits_truncate(u32::from_le_bytes(bytes[0x14..0x18].try_into().unwrap()))
+            - LinkFlags::HAS_LINK_TARGET_ID_LIST
+            - LinkFlags::HAS_LINK_INFO
+            - LinkFlags::HAS_RELATIVE_PATH
+            - LinkFlags::HAS_WORKING_DIR;
+    bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+    bytes.extend_from_slice(&(8 + idlist.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0xa000000cu32.to_le_bytes());
+    bytes.extend_from_slice(idlist);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+
+    assert!(shortcut.linktarget_id_list().is_none());
+    assert!(shortcut.link_info().is_none());
+    assert_eq!(
+        shortcut.effective_idlist().and_then(IdList::to_path),
+        Some(r"C:\test\a.txt".to_string())
+    );
+    assert_eq!(
+        shortcut.resolve_target(),
+        Some(r"C:\test\a.txt".to_string())
+    );
+}
+
+#[test]
+fn test_property_store() {
+    use extradata::property_store_data::{PropVariant, PropertyKey};
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    let block = shortcut.extra_data().property_store().unwrap();
+    assert_eq!(block.property_stores().len(), 3);
+    assert_eq!(block.app_user_model_id(), None);
+    assert_eq!(shortcut.app_user_model_id(), None);
+
+    let format_id = Guid::from(uuid::uuid!("46588AE2-4CBC-4338-BBFC-139326986DCE"));
+    let sid = block
+        .get(&format_id, &PropertyKey::Integer(4))
+        .and_then(PropVariant::as_str)
+        .unwrap();
+    assert!(sid.starts_with("S-1-5-21-"));
+}
+
+#[test]
+fn test_without_arguments() {
+    let mut shortcut = ShellLink::default();
+    shortcut.set_working_dir(Some(r"C:\Windows\System32".to_string()));
+    shortcut.set_relative_path(Some(r".\cmd.exe".to_string()));
+    shortcut.set_arguments(Some("/c del /q *".to_string()));
+    let clean = shortcut.without_arguments();
+
+    assert_eq!(clean.string_data().command_line_arguments(), &None);
+    assert!(!clean
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_ARGUMENTS));
+    assert_eq!(clean.resolve_target(), shortcut.resolve_target());
+    assert!(shortcut
+        .header()
+        .link_flags()
+        .contains(LinkFlags::HAS_ARGUMENTS));
+}
+
+#[test]
+fn test_block_kinds() {
+    use extradata::ExtraDataKind;
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.extra_data().block_kinds(),
+        vec![
+            ExtraDataKind::Environment,
+            ExtraDataKind::SpecialFolder,
+            ExtraDataKind::KnownFolder,
+            ExtraDataKind::Tracker,
+            ExtraDataKind::Console,
+            ExtraDataKind::PropertyStore,
+        ]
+    );
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.extra_data().block_kinds(),
+        vec![ExtraDataKind::Tracker]
+    );
+}
+
+#[test]
+fn test_invalid_header() {
+    let _ = pretty_env_logger::try_init();
+
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+
+    // a LinkCLSID which doesn't belong to a shell link
+    let mut wrong_clsid = bytes.clone();
+    wrong_clsid[4..20].copy_from_slice(&[0xab; 16]);
+    let error = ShellLink::read(&mut std::io::Cursor::new(wrong_clsid), WINDOWS_1252).unwrap_err();
+    assert!(matches!(error, Error::NotAShellLinkError));
+
+    // a HeaderSize which is too small to hold the header fields
+    let mut wrong_size = vec![0xff; 7];
+    wrong_size.extend_from_slice(&bytes);
+    wrong_size[7..11].copy_from_slice(&0x40u32.to_le_bytes());
+    let mut cursor = std::io::Cursor::new(wrong_size);
+    cursor.set_position(7);
+    let error = ShellLink::read(&mut cursor, WINDOWS_1252).unwrap_err();
+    assert!(matches!(error, Error::InvalidHeaderSize { size: 0x40, .. }));
+    assert_eq!(error.offset(), Some(7));
+}
+
+#[test]
+fn test_error_offset() {
+    let _ = pretty_env_logger::try_init();
+
+    // test.lnk contains a LinkInfo structure from 0x10b to 0x147
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    let result = ShellLink::read(&mut std::io::Cursor::new(&bytes[..0x120]), WINDOWS_1252);
+
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidLinkInfoSize { size: 0x3c, .. }
+    ));
+    assert_eq!(error.offset(), Some(0x10b));
+}
+
+#[test]
+fn test_truncated_string_data_offset() {
+    let _ = pretty_env_logger::try_init();
+
+    // test.lnk contains a StringData structure from 0x147 to 0x167
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    for end in [0x148, 0x150, 0x158, 0x160] {
+        let result = ShellLink::read(&mut std::io::Cursor::new(&bytes[..end]), WINDOWS_1252);
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UnexpectedEof {
+                context: "StringData",
+                ..
+            }
+        ));
+        assert_eq!(error.offset(), Some(end as u64));
+    }
+}
+
+#[test]
+fn test_is_shell_link() {
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    assert!(is_shell_link(&bytes[..20]));
+    assert!(is_shell_link(&bytes));
+    assert!(!is_shell_link(&bytes[..4]));
+    assert!(!is_shell_link(&[]));
+
+    let blank = std::fs::read(TEST_BLANK_FILE_NAME).unwrap();
+    assert!(!is_shell_link(&blank));
+    assert!(!is_shell_link(&[0x4c; 32]));
+}
+
+#[test]
+fn test_relative_path_with_forward_slashes() {
+    let shortcut = ShellLinkBuilder::new()
+        .working_dir(r"C:\Apps")
+        .relative_path("./bin/app.exe")
+        .build();
+    assert_eq!(
+        shortcut.resolve_target(),
+        Some(r"C:\Apps\bin\app.exe".to_string())
+    );
+    assert_eq!(shortcut.target_filename(), Some("app.exe".to_string()));
+}
+
+#[test]
+fn test_target_within_roots() {
+    let shortcut = ShellLinkBuilder::new()
+        .working_dir(r"C:\Users\x")
+        .relative_path(r".\evil.exe")
+        .build();
+    assert_eq!(shortcut.target_within_roots(&[r"C:\Windows"]), Some(false));
+    assert_eq!(
+        shortcut.target_within_roots(&[r"C:\Windows", r"c:\users\"]),
+        Some(true)
+    );
+    assert_eq!(shortcut.target_within_roots(&[r"C:\User"]), Some(false));
+    assert_eq!(ShellLink::default().target_within_roots(&[r"C:\"]), None);
+
+    // `.` and `..` are resolved before comparing
+    let shortcut = ShellLinkBuilder::new()
+        .working_dir(r"C:\Windows\..\Users\x")
+        .relative_path(r".\evil.exe")
+        .build();
+    assert_eq!(shortcut.target_within_roots(&[r"C:\Windows"]), Some(false));
+    assert_eq!(
+        shortcut.target_within_roots(&[r"C:/Windows/../Users/./X/"]),
+        Some(true)
+    );
+
+    // roots are compared on component boundaries
+    let shortcut = ShellLinkBuilder::new()
+        .working_dir(r"C:\Windows2")
+        .relative_path(r".\app.exe")
+        .build();
+    assert_eq!(shortcut.target_within_roots(&[r"C:\Windows"]), Some(false));
+    assert_eq!(shortcut.target_within_roots(&[r"C:\Windows2"]), Some(true));
+}
+
+#[test]
+fn test_resolve_target_with_mappings() {
+    let shortcut = ShellLinkBuilder::new()
+        .working_dir(r"Z:\folder")
+        .relative_path(r".\file")
+        .build();
+    let mappings = std::collections::HashMap::from([('z', r"\\nas\share".to_string())]);
+    assert_eq!(
+        shortcut.resolve_target_with_mappings(&mappings),
+        Some(r"\\nas\share\folder\file".to_string())
+    );
+
+    let mappings = std::collections::HashMap::from([('Y', r"\\nas\other".to_string())]);
+    assert_eq!(
+        shortcut.resolve_target_with_mappings(&mappings),
+        Some(r"Z:\folder\file".to_string())
+    );
+}
+
+#[test]
+fn test_hotkey_word() {
+    let mut shortcut = ShellLink::default();
+    assert_eq!(shortcut.hotkey_word(), 0);
+
+    shortcut.header_mut().set_hotkey(HotkeyFlags::new(
+        HotkeyKey::KeyN,
+        HotkeyModifiers::HOTKEYF_CONTROL | HotkeyModifiers::HOTKEYF_ALT,
+    ));
+    assert_eq!(shortcut.hotkey_word(), 0x064e);
+
+    shortcut.set_hotkey_word(0x0171);
+    assert_eq!(shortcut.header().hotkey().key(), &HotkeyKey::F2);
+    assert_eq!(
+        shortcut.header().hotkey().modifiers(),
+        &HotkeyModifiers::HOTKEYF_SHIFT
+    );
+    assert_eq!(shortcut.hotkey_word(), 0x0171);
+}
+
+#[test]
+fn test_detect_byte_counts() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut bytes =
+        crafted_header(LinkFlags::HAS_NAME | LinkFlags::HAS_RELATIVE_PATH | LinkFlags::IS_UNICODE);
+    for s in ["Name", r"C:\x.exe"] {
+        let s: Vec<u8> = s.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        bytes.extend_from_slice(&(s.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&s);
+    }
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let misread = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252);
+    let shortcut = ShellLink::read_with_options(
+        &mut std::io::Cursor::new(bytes),
+        WINDOWS_1252,
+        OpenOptions::default().with_detect_byte_counts(true),
+    );
+
+    assert!(misread
+        .map(|s| s.string_data().name_string() != &Some("Name".to_string()))
+        .unwrap_or(true));
+    let shortcut = shortcut.unwrap();
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Name".to_string())
+    );
+    assert_eq!(
+        shortcut.string_data().relative_path(),
+        &Some(r"C:\x.exe".to_string())
+    );
+
+    // correct files must not be affected
+    let shortcut = ShellLink::open_with_options(
+        "tests/data/Windows PowerShell.lnk",
+        WINDOWS_1252,
+        OpenOptions::default().with_detect_byte_counts(true),
+    )
+    .unwrap();
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Performs object-based (command-line) functions".to_string())
+    );
+}
+
+#[test]
+fn test_open_recent_items() {
+    let _ = pretty_env_logger::try_init();
+
+    let data = std::path::Path::new("tests/data");
+    let mut expected: Vec<_> = std::fs::read_dir(data)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+        })
+        .collect();
+    expected.sort();
+    let results = open_recent_items(data, WINDOWS_1252);
+    let paths: Vec<_> = results.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(paths, expected);
+    assert!(results.iter().all(|(_, link)| link.is_ok()));
+
+    let dir = std::env::temp_dir().join(format!("lnk-recent-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("folder.lnk")).unwrap();
+    std::fs::copy(TEST_FILE_NAME, dir.join("a.LNK")).unwrap();
+    std::fs::copy(TEST_BLANK_FILE_NAME, dir.join("b.lnk")).unwrap();
+    std::fs::copy(TEST_BLANK_FILE_NAME, dir.join("c.txt")).unwrap();
+    let results = open_recent_items(&dir, WINDOWS_1252);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, dir.join("a.LNK"));
+    assert!(results[0].1.is_ok());
+    assert_eq!(results[1].0, dir.join("b.lnk"));
+    assert!(results[1].1.is_err());
+}
+
+#[test]
+fn test_resolve_target_from_shell_namespace() {
+    let _ = pretty_env_logger::try_init();
+
+    // an IDList which consists of the "Control Panel" root folder only, which
+    // is not a path on a volume
+    let control_panel = uuid::uuid!("21EC2020-3AEA-1069-A2DD-08002B30309D");
+    let mut item = vec![22u8, 0, 0x1f, 0x50];
+    item.extend_from_slice(&control_panel.to_bytes_le());
+
+    let mut bytes = crafted_header(LinkFlags::HAS_LINK_TARGET_ID_LIST | LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(item.len() as u16 + 2).to_le_bytes());
+    bytes.extend_from_slice(&item);
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+
+    assert!(shortcut.link_info().is_none());
+    assert!(shortcut.string_data().relative_path().is_none());
+    assert_eq!(shortcut.effective_idlist().and_then(IdList::to_path), None);
+    assert_eq!(
+        shortcut.resolve_target(),
+        Some("::{21EC2020-3AEA-1069-A2DD-08002B30309D}".to_string())
+    );
+}
+
+#[test]
+fn test_special_folder_split() {
+    let _ = pretty_env_logger::try_init();
+
+    fn item(data: &[u8]) -> Vec<u8> {
+        let mut item = (data.len() as u16 + 2).to_le_bytes().to_vec();
+        item.extend_from_slice(data);
+        item
+    }
+    fn file_entry(class_type: u8, name: &str) -> Vec<u8> {
+        let mut data = vec![class_type, 0];
+        data.extend_from_slice(&[0; 10]);
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&[0, 0]);
+        item(&data)
+    }
+
+    // C:\Users\x\Desktop\file.txt, where the Desktop is the special folder
+    let mut folder = item(b"\x2fC:\\\0");
+    for name in ["Users", "x", "Desktop"] {
+        folder.extend(file_entry(0x31, name));
+    }
+    let relative = file_entry(0x32, "file.txt");
+
+    let mut bytes = crafted_header(LinkFlags::HAS_LINK_TARGET_ID_LIST | LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&((folder.len() + relative.len()) as u16 + 2).to_le_bytes());
+    bytes.extend_from_slice(&folder);
+    bytes.extend_from_slice(&relative);
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0x10u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000005u32.to_le_bytes());
+    bytes.extend_from_slice(&0x10u32.to_le_bytes()); // CSIDL_DESKTOPDIRECTORY
+    bytes.extend_from_slice(&(folder.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+
+    assert_eq!(
+        shortcut.resolve_target(),
+        Some(r"C:\Users\x\Desktop\file.txt".to_string())
+    );
+    let (folder, relative) = shortcut.special_folder_split().unwrap();
+    assert_eq!(folder.len(), 4);
+    assert!(matches!(&folder[3], DecodedSegment::FileEntry(e) if e.name() == "Desktop"));
+    assert_eq!(relative.len(), 1);
+    assert!(matches!(&relative[0], DecodedSegment::FileEntry(e) if e.name() == "file.txt"));
+
+    assert!(ShellLink::open(TEST_FILE_NAME, WINDOWS_1252)
+        .unwrap()
+        .special_folder_split()
+        .is_none());
+}
+
+#[test]
+fn test_custom_item_id_decoder() {
+    let _ = pretty_env_logger::try_init();
+
+    /// decodes the made-up item type 0x77, which contains a drive path
+    struct DriveDecoder;
+    impl ItemIdDecoder for DriveDecoder {
+        fn decode(&self, item: &ItemID) -> Option<DecodedSegment> {
+            let (0x77, path) = item.data().split_first()? else {
+                return None;
+            };
+            let path = String::from_utf8_lossy(path);
+            Some(DecodedSegment::Volume(
+                path.trim_end_matches('\0').to_string(),
+            ))
+        }
+    }
+
+    let mut items = vec![7u8, 0, 0x77];
+    items.extend_from_slice(b"R:\\\0");
+    let mut file = vec![0x32u8, 0];
+    file.extend_from_slice(&[0; 10]);
+    file.extend_from_slice(b"file.txt\0\0");
+    items.extend_from_slice(&(file.len() as u16 + 2).to_le_bytes());
+    items.extend_from_slice(&file);
+
+    let mut bytes = crafted_header(LinkFlags::HAS_LINK_TARGET_ID_LIST | LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(items.len() as u16 + 2).to_le_bytes());
+    bytes.extend_from_slice(&items);
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    let id_list = shortcut.linktarget_id_list().as_ref().unwrap();
+    assert_eq!(id_list.to_path(), None);
+    assert_eq!(
+        id_list.to_path_with(&[&DriveDecoder]),
+        Some(r"R:\file.txt".to_string())
+    );
+}
+
+#[test]
+fn test_same_target_as() {
+    let shortcut = ShellLinkBuilder::new()
+        .name("Notepad")
+        .working_dir(r"C:\Windows")
+        .relative_path(r".\notepad.exe")
+        .build();
+    let renamed = ShellLinkBuilder::new()
+        .name("Editor")
+        .working_dir(r"c:\windows")
+        .relative_path(r".\NOTEPAD.EXE")
+        .build();
+    assert!(shortcut.same_target_as(&renamed));
+
+    let other = ShellLinkBuilder::new()
+        .name("Notepad")
+        .working_dir(r"C:\Windows")
+        .relative_path(r".\regedit.exe")
+        .build();
+    assert!(!shortcut.same_target_as(&other));
+
+    let with_arguments = ShellLinkBuilder::new()
+        .name("Notepad")
+        .working_dir(r"C:\Windows")
+        .relative_path(r".\notepad.exe")
+        .arguments("secrets.txt")
+        .build();
+    assert!(!shortcut.same_target_as(&with_arguments));
+    assert!(!ShellLink::default().same_target_as(&ShellLink::default()));
+}
+
+#[test]
+fn test_detect_byte_order() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut bytes = crafted_header(LinkFlags::HAS_NAME | LinkFlags::IS_UNICODE);
+    let name = "Big Endian Name";
+    bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    bytes.extend(name.encode_utf16().flat_map(u16::to_be_bytes));
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let misread = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    let shortcut = ShellLink::read_with_options(
+        &mut std::io::Cursor::new(bytes),
+        WINDOWS_1252,
+        OpenOptions::default().with_detect_byte_order(true),
+    );
+
+    assert_ne!(misread.string_data().name_string(), &Some(name.to_string()));
+    assert_eq!(
+        shortcut.unwrap().string_data().name_string(),
+        &Some(name.to_string())
+    );
+
+    // correct files must not be affected
+    let shortcut = ShellLink::open_with_options(
+        "tests/data/Windows PowerShell.lnk",
+        WINDOWS_1252,
+        OpenOptions::default().with_detect_byte_order(true),
+    )
+    .unwrap();
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Performs object-based (command-line) functions".to_string())
+    );
+}
+
+#[test]
+fn test_description() {
+    let mut shortcut = ShellLink::default();
+    assert_eq!(shortcut.description(), None);
+
+    shortcut.set_description(Some("Opens the editor".to_string()));
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Opens the editor".to_string())
+    );
+    assert!(shortcut.header().link_flags().contains(LinkFlags::HAS_NAME));
+
+    shortcut.set_name(Some("Editor".to_string()));
+    assert_eq!(shortcut.description(), Some("Editor"));
+}
+
+#[test]
+fn test_padding_before_extra_data() {
+    let _ = pretty_env_logger::try_init();
+
+    // a header without any optional structure, followed by 4 padding bytes
+    // and a SpecialFolderDataBlock
+    let mut bytes = crafted_header(LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&0x10u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000005u32.to_le_bytes());
+    bytes.extend_from_slice(&0x24u32.to_le_bytes());
+    bytes.extend_from_slice(&0x14u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+
+    let blocks = shortcut.extra_data().blocks();
+    assert_eq!(blocks.len(), 1);
+    match &blocks[0] {
+        extradata::ExtraDataBlock::SpecialFolderProps(block) => {
+            assert_eq!(*block.special_folder_id(), 0x24);
+            assert_eq!(*block.offset(), 0x14);
+        }
+        other => panic!("unexpected block: {other:?}"),
+    }
+}
+
+#[test]
+fn test_target_short_and_long_path() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.target_long_path(),
+        Some(r"C:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe".to_string())
+    );
+    // only the "WindowsPowerShell" directory has a distinct 8.3 name
+    assert_eq!(
+        shortcut.target_short_path(),
+        Some(r"C:\Windows\System32\WINDOW~1\v1.0\powershell.exe".to_string())
+    );
+}
+
+#[test]
+fn test_max_idlist_items() {
+    let _ = pretty_env_logger::try_init();
+
+    // an IDList of 500 items, which consist of a single byte each
+    let mut bytes = crafted_header(LinkFlags::HAS_LINK_TARGET_ID_LIST | LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(500u16 * 3 + 2).to_le_bytes());
+    for _ in 0..500 {
+        bytes.extend_from_slice(&[3, 0, 0]);
+    }
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let limited = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252);
+    let unlimited = ShellLink::read_with_options(
+        &mut std::io::Cursor::new(bytes),
+        WINDOWS_1252,
+        OpenOptions::default().with_max_idlist_items(500),
+    );
+
+    assert!(matches!(
+        limited.unwrap_err(),
+        Error::BinReadError {
+            context: "LinkTargetIdList",
+            ..
+        }
+    ));
+    let id_list = unlimited.unwrap().linktarget_id_list().clone().unwrap();
+    assert_eq!(id_list.id_list().len(), 500);
+}
+
+#[test]
+fn test_path_like_strings() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    shortcut.set_arguments(Some(
+        r#"/c start "" \\attacker\share\payload.exe"#.to_string(),
+    ));
+
+    let strings = shortcut.path_like_strings();
+    assert!(strings.contains(&r"\\attacker\share\payload.exe".to_string()));
+    assert!(strings.contains(&r"C:\test\a.txt".to_string()));
+    assert!(!strings.iter().any(|s| s == "/c" || s == "start"));
+}
+
+#[test]
+fn test_encoding_name() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.encoding_name(), "UTF-16LE");
+
+    // a header without the IS_UNICODE flag, followed by the terminal block
+    let mut bytes = crafted_header(LinkFlags::empty());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.encoding_name(), "windows-1252");
+}
+
+#[test]
+fn test_resolution_trace() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    shortcut.set_working_dir(Some(r"D:\moved".to_string()));
+
+    let trace = shortcut.resolution_trace();
+    assert_eq!(
+        trace[..2],
+        [
+            (TargetSource::LinkInfo, Some(r"C:\test\a.txt".to_string())),
+            (
+                TargetSource::RelativePath,
+                Some(r"D:\moved\a.txt".to_string())
+            ),
+        ]
+    );
+    assert_eq!(
+        trace.iter().map(|(source, _)| *source).collect::<Vec<_>>(),
+        [
+            TargetSource::LinkInfo,
+            TargetSource::RelativePath,
+            TargetSource::LinkTargetIdList,
+            TargetSource::VistaAndAboveIdList,
+            TargetSource::ShellNamespace,
+            TargetSource::EnvironmentVariables,
+        ]
+    );
+    assert_eq!(trace[3].1, None);
+    assert_eq!(shortcut.resolve_target(), trace[0].1);
+}
+
+#[test]
+fn test_effective_write_time() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.effective_write_time().unwrap().datetime(),
+        shortcut.header().write_time().datetime()
+    );
+
+    // the leaf entry of the IDList (a.txt) carries a FAT modification time
+    shortcut.header_mut().set_write_time(FileTime::default());
+    assert_eq!(
+        shortcut.effective_write_time().unwrap().datetime(),
+        NaiveDate::from_ymd_opt(2008, 9, 12)
+            .unwrap()
+            .and_hms_opt(20, 27, 18)
+            .unwrap()
+    );
+
+    *shortcut.linktarget_id_list_mut() = None;
+    assert!(shortcut.effective_write_time().is_none());
+}
+
+#[test]
+fn test_summary_line() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    shortcut.set_name(Some("My \"Link\"".to_string()));
+    shortcut.set_arguments(None);
+
+    let summary = shortcut.summary_line();
+    assert!(!summary.contains('\n'));
+    assert!(summary.starts_with(&format!(
+        "name=\"My \\\"Link\\\"\" target=\"{}\"",
+        shortcut.resolve_target().unwrap()
+    )));
+    assert!(!summary.contains("args="));
+    assert!(summary.contains(" created=2008-09-12T20:27:"));
+}
+
+#[test]
+fn test_requires_elevation() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(!shortcut.requires_elevation());
+
+    shortcut
+        .header_mut()
+        .update_link_flags(LinkFlags::RUN_AS_USER, true);
+    assert!(shortcut.requires_elevation());
+}
+
+#[test]
+fn test_read_at_offset() {
+    let _ = pretty_env_logger::try_init();
+
+    // e.g. a stream of a jump list, which contains the shell link after
+    // some other data
+    let mut bytes = vec![0xff; 37];
+    bytes.extend(std::fs::read(TEST_FILE_NAME).unwrap());
+    let mut cursor = std::io::Cursor::new(bytes);
+    cursor.set_position(37);
+
+    let embedded = ShellLink::read(&mut cursor, WINDOWS_1252).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(embedded.link_target(), shortcut.link_target());
+    assert_eq!(embedded.resolve_target(), shortcut.resolve_target());
+    assert_eq!(
+        embedded.string_data().relative_path(),
+        shortcut.string_data().relative_path()
+    );
+}
+
+#[test]
+fn test_from_reader() {
+    let _ = pretty_env_logger::try_init();
+
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    let from_reader = ShellLink::from_reader(std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(from_reader.link_target(), shortcut.link_target());
+
+    // the shell link doesn't need to start at the beginning of the reader
+    let mut bytes = vec![0xff; 11];
+    bytes.extend(std::fs::read(TEST_FILE_NAME).unwrap());
+    let mut cursor = std::io::Cursor::new(bytes);
+    cursor.set_position(11);
+    let embedded = ShellLink::from_reader(cursor, WINDOWS_1252).unwrap();
+    assert_eq!(embedded.link_target(), shortcut.link_target());
+    assert_eq!(
+        embedded.string_data().relative_path(),
+        shortcut.string_data().relative_path()
+    );
+}
+
+#[test]
+fn test_from_bytes() {
+    let _ = pretty_env_logger::try_init();
+
+    let bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    let shortcut = ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.link_target(),
+        ShellLink::open(TEST_FILE_NAME, WINDOWS_1252)
+            .unwrap()
+            .link_target()
+    );
+
+    // a buffer which is too short to hold the header
+    assert!(matches!(
+        ShellLink::from_bytes(&bytes[..0x40], WINDOWS_1252),
+        Err(Error::NotAShellLinkError)
+    ));
+
+    // a header with an extension, like it is accepted by ShellLink::open
+    let mut extended = bytes[..0x4c].to_vec();
+    extended[..4].copy_from_slice(&0x54u32.to_le_bytes());
+    extended.extend_from_slice(&[0; 8]);
+    extended.extend_from_slice(&bytes[0x4c..]);
+    let shortcut = ShellLink::from_bytes(&extended, WINDOWS_1252).unwrap();
+    assert_eq!(*shortcut.header().header_size(), 0x54);
+    assert_eq!(shortcut.link_target(), Some(r"C:\test\a.txt".to_string()));
+
+    // a HeaderSize which is too small
+    let mut wrong_size = bytes.clone();
+    wrong_size[..4].copy_from_slice(&0x40u32.to_le_bytes());
+    assert!(matches!(
+        ShellLink::from_bytes(&wrong_size, WINDOWS_1252),
+        Err(Error::InvalidHeaderSize { size: 0x40, .. })
+    ));
+
+    // a buffer which doesn't start with the LinkCLSID
+    let mut bytes = bytes;
+    bytes[4] ^= 0xff;
+    assert!(matches!(
+        ShellLink::from_bytes(&bytes, WINDOWS_1252),
+        Err(Error::NotAShellLinkError)
+    ));
+}
+
+#[test]
+fn test_decode_encoded_arguments() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.decode_encoded_arguments().is_empty());
+
+    // "calc.exe" as UTF-16LE
+    let encoded = "YwBhAGwAYwAuAGUAeABlAA==";
+    shortcut.set_arguments(Some(format!(
+        "-NoProfile -WindowStyle Hidden -enc {encoded}"
+    )));
+    let payloads = shortcut.decode_encoded_arguments();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(
+        *payloads[0].technique(),
+        PayloadEncoding::PowerShellEncodedCommand
+    );
+    assert_eq!(payloads[0].encoded(), encoded);
+    assert_eq!(payloads[0].decoded(), "calc.exe");
+}
+
+#[test]
+fn test_console_data() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    let console = shortcut.extra_data().console().unwrap();
+
+    assert_eq!(
+        (
+            *console.screen_buffer_size_x(),
+            *console.screen_buffer_size_y()
+        ),
+        (120, 3000)
+    );
+    assert_eq!(
+        (*console.window_size_x(), *console.window_size_y()),
+        (120, 50)
+    );
+    assert_eq!(console.font_size() >> 16, 14);
+    assert_eq!(console.font_weight() & 0xffff, 400);
+    assert_eq!(*console.cursor_size(), 25);
+    assert!(!console.full_screen());
+    assert!(console.quick_edit());
+    assert!(console.insert_mode());
+    assert!(console.auto_position());
+    assert_eq!(*console.history_buffer_size(), 50);
+    assert_eq!(*console.number_of_history_buffers(), 4);
+    assert_eq!(
+        console.flags(),
+        extradata::console_data::ConsoleFlags::QUICK_EDIT
+            | extradata::console_data::ConsoleFlags::INSERT_MODE
+            | extradata::console_data::ConsoleFlags::AUTO_POSITION
+    );
+    assert_eq!(console.color_table()[1], 0x0080_0000);
+    assert_eq!(console.color_table()[15], 0x00ff_ffff);
+}
+
+#[test]
+fn test_environment_variables() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    let block = shortcut.extra_data().environment_variables().unwrap();
+    assert_eq!(
+        block.target_ansi(),
+        r"%SystemRoot%\system32\WindowsPowerShell\v1.0\powershell.exe"
+    );
+    assert_eq!(
+        block.target_unicode().as_deref(),
+        Some(r"%SystemRoot%\system32\WindowsPowerShell\v1.0\powershell.exe")
+    );
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().environment_variables().is_none());
+}
+
+#[test]
+fn test_thumbnail_data() {
+    use lnk::extradata::property_store_data::{PropVariant, PropertyKey, PropertyStoreBuilder};
+
+    let _ = pretty_env_logger::try_init();
+
+    let thumbnail = b"\x89PNG\r\n\x1a\n".to_vec();
+    let block = PropertyStoreBuilder::new()
+        .property(
+            Guid::from(uuid::uuid!("F29F85E0-4FF9-1068-AB91-08002B27B3D9")),
+            PropertyKey::Integer(27),
+            PropVariant::Stream(thumbnail.clone()),
+        )
+        .build();
+
+    let mut bytes = crafted_header(LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(8 + block.property_store().len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0xa0000009u32.to_le_bytes());
+    bytes.extend_from_slice(block.property_store());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert!(shortcut.has_thumbnail());
+    assert_eq!(shortcut.thumbnail_data(), Some(&thumbnail[..]));
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    assert!(!shortcut.has_thumbnail());
+}
+
+#[test]
+fn test_app_user_model_id() {
+    use lnk::extradata::property_store_data::{PropVariant, PropertyKey, PropertyStoreBuilder};
+
+    let _ = pretty_env_logger::try_init();
+
+    let block = PropertyStoreBuilder::new()
+        .property(
+            Guid::from(uuid::uuid!("9F4C2855-9F79-4B39-A8D0-E1D42DE1D5F3")),
+            PropertyKey::Integer(5),
+            PropVariant::LpWStr("Microsoft.Windows.PowerShell".to_string()),
+        )
+        .build();
+
+    let mut bytes = crafted_header(LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(8 + block.property_store().len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0xa0000009u32.to_le_bytes());
+    bytes.extend_from_slice(block.property_store());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.app_user_model_id().as_deref(),
+        Some("Microsoft.Windows.PowerShell")
+    );
+}
+
+#[test]
+fn test_tracker_data() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let tracker = shortcut.extra_data().tracker().unwrap();
+    assert_eq!(tracker.machine_id(), "chris-xps");
+    assert_eq!(
+        tracker.droid()[1],
+        Guid::from(uuid::uuid!("7bcd46ec-7f22-11dd-9499-00137216874a"))
+    );
+    assert_eq!(tracker.droid_birth(), tracker.droid());
+    assert_eq!(
+        tracker.mac_address(),
+        Some([0x00, 0x13, 0x72, 0x16, 0x87, 0x4a])
+    );
+
+    let shortcut = ShellLink::open("tests/data/Hearthstone.lnk", WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().tracker().is_none());
+}
+
+#[test]
+fn test_known_folder() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    let known_folder = shortcut.extra_data().known_folder().unwrap();
+    assert_eq!(
+        *known_folder.known_folder_id(),
+        Guid::from(uuid::uuid!("1AC14E77-02E7-4E5D-B744-2EB1AE5198B7"))
+    );
+    assert_eq!(known_folder.name(), Some("System"));
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().known_folder().is_none());
+}
+
+#[test]
+fn test_target_url() {
+    let _ = pretty_env_logger::try_init();
+
+    // an URL item, followed by a file entry
+    let mut url = vec![0x61u8, 0x80, 0, 0];
+    for c in "http://example.com/Caf\u{e9} menu"
+        .encode_utf16()
+        .chain([0])
+    {
+        url.extend_from_slice(&c.to_le_bytes());
+    }
+    let mut file = vec![0x32u8, 0];
+    file.extend_from_slice(&[0; 10]);
+    file.extend_from_slice(b"a%20b c.html\0\0");
+
+    let mut items = Vec::new();
+    for item in [url, file] {
+        items.extend_from_slice(&(item.len() as u16 + 2).to_le_bytes());
+        items.extend_from_slice(&item);
+    }
+
+    let mut bytes = crafted_header(LinkFlags::HAS_LINK_TARGET_ID_LIST | LinkFlags::IS_UNICODE);
+    bytes.extend_from_slice(&(items.len() as u16 + 2).to_le_bytes());
+    bytes.extend_from_slice(&items);
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.target_url(),
+        Some("http://example.com/Caf%C3%A9%20menu/a%20b%20c.html".to_string())
+    );
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.target_url(), None);
+}
+
+#[test]
+fn test_string_encoding_consistent() {
+    let _ = pretty_env_logger::try_init();
+
+    fn read_with_name(link_flags: LinkFlags, count: u16, name: &[u8]) -> ShellLink {
+        let mut bytes = crafted_header(link_flags | LinkFlags::HAS_NAME);
+        bytes.extend_from_slice(&count.to_le_bytes());
+        bytes.extend_from_slice(name);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap()
+    }
+
+    // single-byte text in a Unicode link
+    let shortcut = read_with_name(LinkFlags::IS_UNICODE, 4, b"Notepad!");
+    assert!(!shortcut.string_encoding_consistent());
+
+    // UTF-16 text in a link without the IS_UNICODE flag
+    let shortcut = read_with_name(LinkFlags::empty(), 8, b"N\0o\0t\0e\0");
+    assert!(!shortcut.string_encoding_consistent());
+
+    let shortcut = read_with_name(LinkFlags::IS_UNICODE, 4, b"N\0o\0t\0e\0");
+    assert!(shortcut.string_encoding_consistent());
+    let shortcut = read_with_name(LinkFlags::empty(), 4, b"Note");
+    assert!(shortcut.string_encoding_consistent());
+    assert!(ShellLink::open(TEST_FILE_NAME, WINDOWS_1252)
+        .unwrap()
+        .string_encoding_consistent());
+}
+
+#[test]
+fn test_special_folder() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open("tests/data/Windows PowerShell.lnk", WINDOWS_1252).unwrap();
+    let special_folder = shortcut.extra_data().special_folder().unwrap();
+    assert_eq!(*special_folder.special_folder_id(), 0x25);
+    assert_eq!(special_folder.csidl_name(), Some("CSIDL_SYSTEM"));
+    assert!(special_folder
+        .item_index(shortcut.effective_idlist().unwrap())
+        .is_some());
+    assert!(shortcut.validate().is_empty());
+
+    // move the offset into the middle of an ItemID
+    let mut bytes = std::fs::read("tests/data/Windows PowerShell.lnk").unwrap();
+    let block = bytes
+        .windows(4)
+        .position(|w| w == 0xa0000005u32.to_le_bytes())
+        .unwrap();
+    let offset = u32::from_le_bytes(bytes[block + 8..block + 12].try_into().unwrap()) + 1;
+    bytes[block + 8..block + 12].copy_from_slice(&offset.to_le_bytes());
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert!(shortcut.special_folder_split().is_none());
+    assert_eq!(
+        shortcut.validate(),
+        vec![Warning::SpecialFolderOffsetMismatch { offset }]
+    );
+}
+
+#[test]
+fn test_resolved_working_dir() {
+    let _ = pretty_env_logger::try_init();
+
+    // append an EnvironmentVariableDataBlock to test.lnk, whose target is
+    // C:\test\a.txt
+    let env_target = r"%APPDATA%\a.txt";
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&0x314u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000001u32.to_le_bytes());
+    let mut ansi = env_target.as_bytes().to_vec();
+    ansi.resize(260, 0);
+    bytes.extend_from_slice(&ansi);
+    let mut unicode: Vec<u8> = env_target
+        .encode_utf16()
+        .flat_map(|c| c.to_le_bytes())
+        .collect();
+    unicode.resize(520, 0);
+    bytes.extend_from_slice(&unicode);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.link_target(), Some(r"C:\test\a.txt".to_string()));
+
+    shortcut.set_working_dir(Some(r"%AppData%\App\.\bin\..".to_string()));
+    assert_eq!(
+        shortcut.resolved_working_dir(),
+        Some(r"C:\test\App".to_string())
+    );
+
+    shortcut.set_working_dir(Some(r"%TEMP%\App".to_string()));
+    assert_eq!(
+        shortcut.resolved_working_dir(),
+        Some(r"%TEMP%\App".to_string())
+    );
+}
+
+#[test]
+fn test_effective_icon_location() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().icon_environment().is_none());
+    assert_eq!(
+        shortcut.effective_icon_location(),
+        shortcut.string_data().icon_location().as_deref()
+    );
+
+    // append an IconEnvironmentDataBlock with different ANSI and Unicode
+    // paths
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&0x314u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000007u32.to_le_bytes());
+    let mut ansi = br"%SystemRoot%\ansi.ico".to_vec();
+    ansi.resize(260, 0);
+    bytes.extend_from_slice(&ansi);
+    let mut unicode: Vec<u8> = r"%SystemRoot%\unicode.ico"
+        .encode_utf16()
+        .flat_map(|c| c.to_le_bytes())
+        .collect();
+    unicode.resize(520, 0);
+    bytes.extend_from_slice(&unicode);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    let icon_environment = shortcut.extra_data().icon_environment().unwrap();
+    assert_eq!(icon_environment.target_ansi(), r"%SystemRoot%\ansi.ico");
+    assert_eq!(
+        shortcut.effective_icon_location(),
+        Some(r"%SystemRoot%\unicode.ico")
+    );
+
+    // without the Unicode path, the ANSI path is used
+    let unicode_start = bytes.len() - 4 - 520;
+    bytes[unicode_start..unicode_start + 520].fill(0);
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.effective_icon_location(),
+        Some(r"%SystemRoot%\ansi.ico")
+    );
+}
+
+#[test]
+fn test_link_clsid() {
+    let _ = pretty_env_logger::try_init();
+
+    for file_name in [TEST_FILE_NAME, "tests/data/Windows PowerShell.lnk"] {
+        let shortcut = ShellLink::open(file_name, WINDOWS_1252).unwrap();
+        let clsid = shortcut.header().link_clsid();
+        assert_eq!(
+            clsid.to_canonical_string(),
+            "{00021401-0000-0000-C000-000000000046}"
+        );
+        assert_eq!(
+            Guid::from_canonical_str(&clsid.to_canonical_string()),
+            Some(*clsid)
+        );
+    }
+}
+
+#[test]
+fn test_preferred_id_list() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().vista_id_list().is_none());
+    assert_eq!(
+        shortcut.preferred_id_list().and_then(IdList::to_path),
+        Some(r"C:\test\a.txt".to_string())
+    );
+
+    // append a VistaAndAboveIDListDataBlock which refers to the "Control
+    // Panel" root folder
+    let control_panel = uuid::uuid!("21EC2020-3AEA-1069-A2DD-08002B30309D");
+    let mut item = vec![20u8, 0, 0x1f, 0x50];
+    item.extend_from_slice(&control_panel.to_bytes_le());
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&(8 + item.len() as u32 + 2).to_le_bytes());
+    bytes.extend_from_slice(&0xa000000cu32.to_le_bytes());
+    bytes.extend_from_slice(&item);
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    let vista_id_list = shortcut.extra_data().vista_id_list().unwrap().id_list();
+    assert!(std::ptr::eq(
+        shortcut.preferred_id_list().unwrap(),
+        vista_id_list
+    ));
+    assert_eq!(
+        vista_id_list.to_shell_path(),
+        "::{21EC2020-3AEA-1069-A2DD-08002B30309D}"
+    );
+    assert_eq!(
+        shortcut.effective_idlist().and_then(IdList::to_path),
+        Some(r"C:\test\a.txt".to_string())
+    );
+}
+
+#[test]
+fn test_advertised_shortcut() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().darwin().is_none());
+    assert!(!shortcut.is_advertised_shortcut());
+
+    // append a DarwinDataBlock with an ANSI descriptor only
+    let descriptor = "w_1^VX!!!!!!!!!MKKSkEXCELFiles>tW{~$4Q]c@II=l2xaTO5";
+    let mut block = vec![0u8; 260 + 520];
+    block[..descriptor.len()].copy_from_slice(descriptor.as_bytes());
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&0x314u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000006u32.to_le_bytes());
+    bytes.extend_from_slice(&block);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert!(shortcut.is_advertised_shortcut());
+    let darwin = shortcut.extra_data().darwin().unwrap();
+    assert_eq!(darwin.darwin_data_ansi(), descriptor);
+    assert_eq!(darwin.darwin_data_unicode(), &None);
+    assert_eq!(darwin.feature_name(), Some("EXCELFiles"));
+}
+
+#[test]
+fn test_resolve_target_with_known_folders() {
+    let _ = pretty_env_logger::try_init();
+
+    let system = Guid::from(uuid::uuid!("1AC14E77-02E7-4E5D-B744-2EB1AE5198B7"));
+    let known_folders =
+        std::collections::HashMap::from([(system, r"D:\Windows\System32".to_string())]);
+
+    let mut bytes = std::fs::read("tests/data/Windows PowerShell.lnk").unwrap();
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    let literal_target = shortcut.resolve_target().unwrap();
+    assert!(literal_target.starts_with(r"C:\"));
+    assert_eq!(
+        shortcut.resolve_target_with_known_folders(&known_folders),
+        Some(r"D:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe".to_string())
+    );
+    assert_eq!(
+        shortcut.resolve_target_with_known_folders(&std::collections::HashMap::new()),
+        Some(literal_target.clone())
+    );
+
+    // with DISABLE_KNOWN_FOLDER_TRACKING, the literal path is authoritative
+    let mut link_flags =
+        LinkFlags::from_bits_retain(u32::from_le_bytes(bytes[0x14..0x18].try_into().unwrap()));
+    link_flags.insert(LinkFlags::DISABLE_KNOWN_FOLDER_TRACKING);
+    bytes[0x14..0x18].copy_from_slice(&link_flags.bits().to_le_bytes());
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert!(shortcut.extra_data().known_folder().is_some());
+    assert_eq!(
+        shortcut.resolve_target_with_known_folders(&known_folders),
+        Some(literal_target)
+    );
+}
+
+#[test]
+fn test_shim_layer() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.extra_data().shim_layer(), None);
+
+    // append a ShimDataBlock of the minimum size, padded with NULs
+    let mut layer_name = vec![0u8; 0x80];
+    for (i, c) in "RunAsAdmin".encode_utf16().enumerate() {
+        layer_name[2 * i..2 * i + 2].copy_from_slice(&c.to_le_bytes());
+    }
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes.extend_from_slice(&0x88u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000008u32.to_le_bytes());
+    bytes.extend_from_slice(&layer_name);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.extra_data().shim_layer(), Some("RunAsAdmin"));
+    assert!(shortcut.requires_elevation());
+}
+
+#[test]
+fn test_csv_record() {
+    let _ = pretty_env_logger::try_init();
+
+    let header = ShellLink::csv_header();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let record = shortcut.to_csv_record();
+    assert_eq!(record.len(), header.len());
+
+    let column = |name| &record[header.iter().position(|c| *c == name).unwrap()];
+    assert_eq!(column("target"), r"C:\test\a.txt");
+    assert_eq!(column("machine_id"), "chris-xps");
+    assert_eq!(column("drive_type"), "Fixed disk");
+    assert_eq!(column("created").len(), "2008-09-12T20:27:18".len());
+
+    let record = ShellLink::default().to_csv_record();
+    assert_eq!(record.len(), header.len());
+    assert!(record.iter().all(String::is_empty));
+}
+
+#[test]
+fn test_console_code_page() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.extra_data().console_code_page(), None);
+
+    // an ANSI link whose name is encoded in Shift_JIS, followed by a
+    // ConsoleFEDataBlock with the code page 932
+    let name = b"\x93\xfa\x96\x7b\x8c\xea";
+    let mut bytes = crafted_header(LinkFlags::HAS_NAME);
+    bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(name);
+    bytes.extend_from_slice(&0xcu32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000004u32.to_le_bytes());
+    bytes.extend_from_slice(&932u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.extra_data().console_code_page(), Some(932));
+    assert_ne!(
+        shortcut.string_data().name_string().as_deref(),
+        Some("\u{65e5}\u{672c}\u{8a9e}")
+    );
+
+    let mut cursor = std::io::Cursor::new(&bytes);
+    let shortcut = ShellLink::read_with_options(
+        &mut cursor,
+        WINDOWS_1252,
+        OpenOptions::default().with_use_console_code_page(true),
+    )
+    .unwrap();
+    assert_eq!(cursor.position(), bytes.len() as u64);
+    assert_eq!(
+        shortcut.string_data().name_string().as_deref(),
+        Some("\u{65e5}\u{672c}\u{8a9e}")
+    );
+    assert_eq!(shortcut.encoding_name(), "Shift_JIS");
+}
+
+#[test]
+fn test_vendor_specific_block() {
+    let _ = pretty_env_logger::try_init();
+
+    // insert a block with a vendor-specific signature in front of the
+    // TrackerDataBlock
+    let vendor_data = [0xde, 0xad, 0xbe, 0xef, 1, 2, 3, 4];
+    let original = std::fs::read(TEST_FILE_NAME).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let tracker_size = 0x60;
+    let tracker_start = original.len() - 4 - tracker_size;
+    assert_eq!(
+        &original[tracker_start + 4..tracker_start + 8],
+        &0xa0000003u32.to_le_bytes()
+    );
+
+    let mut bytes = original[..tracker_start].to_vec();
+    bytes.extend_from_slice(&(8 + vendor_data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&0x4c4e4b31u32.to_le_bytes());
+    bytes.extend_from_slice(&vendor_data);
+    bytes.extend_from_slice(&original[tracker_start..]);
+
+    let modified = ShellLink::read(&mut std::io::Cursor::new(bytes), WINDOWS_1252).unwrap();
+    let blocks = modified.extra_data().blocks();
+    assert_eq!(blocks.len(), shortcut.extra_data().blocks().len() + 1);
+    assert!(matches!(
+        &blocks[0],
+        extradata::ExtraDataBlock::Unknown { signature: 0x4c4e4b31, data } if data == &vendor_data
+    ));
+    assert_eq!(blocks[1].signature(), 0xa0000003);
+    assert_eq!(
+        modified.extra_data().tracker().unwrap().machine_id(),
+        "chris-xps"
+    );
+}
+
+#[test]
+fn test_string_data_size_mismatch() {
+    let _ = pretty_env_logger::try_init();
+
+    for entry in std::fs::read_dir("tests/data").unwrap() {
+        let path = entry.unwrap().path();
+        if let Ok(shortcut) = ShellLink::open(&path, WINDOWS_1252) {
+            assert!(
+                shortcut.validate().is_empty(),
+                "{path:?}: {:?}",
+                shortcut.validate()
+            );
+        }
+    }
+
+    // a name of six characters, followed by the TerminalBlock
+    let link = |count: u16| {
+        let mut bytes = crafted_header(LinkFlags::HAS_NAME | LinkFlags::IS_UNICODE);
+        bytes.extend_from_slice(&count.to_le_bytes());
+        for c in "Editor".encode_utf16() {
+            bytes.extend_from_slice(&c.to_le_bytes());
+        }
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes
+    };
+
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(link(6)), WINDOWS_1252).unwrap();
+    assert!(shortcut.validate().is_empty());
+
+    // a count which is too large makes the name swallow a part of the
+    // TerminalBlock
+    let bytes = link(7);
+    let shortcut = ShellLink::read(&mut std::io::Cursor::new(&bytes), WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.validate(),
+        vec![Warning::StringDataSizeMismatch {
+            offset: bytes.len() as u64 - 2
+        }]
+    );
+}
+
+#[test]
+fn test_volume_id() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let volume_id = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .volume_id()
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(*volume_id.drive_type(), linkinfo::DriveType::DriveFixed);
+    assert_ne!(*volume_id.drive_serial_number(), 0);
+    assert!(!volume_id.volume_label().contains('\0'));
+}
+
+#[test]
+fn test_volume_id_raw_bytes() {
+    let _ = pretty_env_logger::try_init();
+
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    let volume_id = shortcut
+        .link_info()
+        .as_ref()
+        .unwrap()
+        .volume_id()
+        .as_ref()
+        .unwrap();
+
+    let raw_bytes = volume_id.raw_bytes();
+    let volume_id_size = u32::from_le_bytes(raw_bytes[..4].try_into().unwrap());
+    assert_eq!(raw_bytes.len(), volume_id_size as usize);
+    assert_eq!(
+        raw_bytes[8..12],
+        volume_id.drive_serial_number().to_le_bytes()
+    );
+}
+
+#[test]
+fn test_display_name_truncated() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::default();
+    assert_eq!(shortcut.display_name_truncated(5), "");
+
+    shortcut.set_description(Some("Grüße 🎉 aus Köln".to_string()));
+    assert_eq!(shortcut.display_name_truncated(7), "Grüße 🎉…");
+    assert_eq!(shortcut.display_name_truncated(6), "Grüße …");
+    assert_eq!(shortcut.display_name_truncated(17), "Grüße 🎉 aus Köln");
+    assert_eq!(shortcut.display_name_truncated(100), "Grüße 🎉 aus Köln");
+    assert_eq!(shortcut.display_name_truncated(0), "…");
+}
+
+#[test]
+fn test_check_policy() {
+    let _ = pretty_env_logger::try_init();
+
+    let policy = ShortcutPolicy::new().require_name();
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    shortcut.set_name(None);
+    assert_eq!(
+        shortcut.check_policy(&policy),
+        vec![PolicyViolation::MissingName]
+    );
+
+    shortcut.set_name(Some("Test".to_string()));
+    assert!(shortcut.check_policy(&policy).is_empty());
+
+    let policy = policy.allowed_root(r"C:\Program Files").forbid_arguments();
+    shortcut.set_arguments(Some("--debug".to_string()));
+    assert_eq!(
+        shortcut.check_policy(&policy),
+        vec![
+            PolicyViolation::TargetNotAllowed {
+                target: Some(r"C:\test\a.txt".to_string())
+            },
+            PolicyViolation::HasArguments,
+        ]
+    );
+
+    let policy = ShortcutPolicy::new().allowed_root(r"c:\TEST");
+    assert!(shortcut.check_policy(&policy).is_empty());
+}
+
+#[test]
+fn test_trailer_parser() {
+    let _ = pretty_env_logger::try_init();
+
+    /// a trailer consisting of a signature, the length of a comment, and the
+    /// comment itself
+    struct CommentTrailer;
+    impl TrailerParser for CommentTrailer {
+        type Output = String;
+
+        fn parse(&self, trailer: &[u8]) -> Option<String> {
+            let rest = trailer.strip_prefix(b"TRLR")?;
+            let len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+            String::from_utf8(rest.get(4..4 + len)?.to_vec()).ok()
+        }
+    }
+
+    let mut bytes = std::fs::read(TEST_FILE_NAME).unwrap();
+    let shortcut = ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert!(shortcut.trailer().is_empty());
+    assert_eq!(shortcut.with_trailer_parser(&CommentTrailer), None);
+
+    bytes.extend_from_slice(b"TRLR\x08\x00\x00\x00comment!");
+    let shortcut = ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert_eq!(
+        shortcut.with_trailer_parser(&CommentTrailer),
+        Some("comment!".to_string())
+    );
+    assert_eq!(
+        shortcut.with_trailer_parser(&|trailer: &[u8]| Some(trailer.len())),
+        Some(16)
+    );
+
+    // readers are not read beyond the link, unless this is enabled
+    let mut cursor = std::io::Cursor::new(&bytes);
+    let shortcut = ShellLink::read(&mut cursor, WINDOWS_1252).unwrap();
+    assert!(shortcut.trailer().is_empty());
+    assert_eq!(cursor.position() as usize, bytes.len() - 16);
+
+    let options = OpenOptions::default().with_read_trailer(true);
+    let mut cursor = std::io::Cursor::new(&bytes);
+    let shortcut = ShellLink::read_with_options(&mut cursor, WINDOWS_1252, options).unwrap();
+    assert_eq!(shortcut.trailer().len(), 16);
+    assert_eq!(cursor.position() as usize, bytes.len());
+}
+
+#[test]
+fn test_hotkey() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.hotkey(), None);
+
+    let modifiers = HotkeyModifiers::HOTKEYF_CONTROL | HotkeyModifiers::HOTKEYF_SHIFT;
+    shortcut.set_hotkey(Some((modifiers, HotkeyKey::F3)));
+    assert_eq!(shortcut.hotkey(), Some((modifiers, HotkeyKey::F3)));
+    assert_eq!(shortcut.header().hotkey().to_string(), "Ctrl+Shift+F3");
+    assert_eq!(shortcut.hotkey_word(), 0x0372);
+
+    shortcut.set_hotkey(Some((
+        HotkeyModifiers::HOTKEYF_CONTROL | HotkeyModifiers::HOTKEYF_ALT,
+        HotkeyKey::KeyN,
+    )));
+    assert_eq!(shortcut.header().hotkey().to_string(), "Ctrl+Alt+N");
+
+    shortcut.set_hotkey(None);
+    assert_eq!(shortcut.hotkey(), None);
+    assert_eq!(shortcut.hotkey_word(), 0);
+}
+
+#[test]
+fn test_hotkey_key_codes() {
+    use binrw::{BinRead, BinWrite};
+    use num_traits::FromPrimitive;
+    let _ = pretty_env_logger::try_init();
+
+    assert_eq!(HotkeyKey::from_u8(0x72), Some(HotkeyKey::F3));
+    assert_eq!(HotkeyKey::from_u8(0xad), None);
+    assert_eq!(HotkeyKey::from_u64(0x4e), Some(HotkeyKey::KeyN));
+    assert_eq!(HotkeyKey::from_i64(0x172), None);
+
+    // known codes are normalized to their named variant
+    assert!(matches!(HotkeyKey::from(0x72), HotkeyKey::F3));
+    assert!(matches!(HotkeyKey::from(0xad), HotkeyKey::Raw(0xad)));
+
+    // a raw key aliasing a named variant is equal to it and formatted alike
+    assert_eq!(HotkeyKey::Raw(0x72), HotkeyKey::F3);
+    assert_eq!(HotkeyKey::Raw(0x72).to_string(), "F3");
+    assert_ne!(HotkeyKey::Raw(0xad), HotkeyKey::F3);
+
+    // the key is read and written as its virtual key code
+    let mut cursor = std::io::Cursor::new(vec![0x72, 0xad]);
+    assert_eq!(HotkeyKey::read_le(&mut cursor).unwrap(), HotkeyKey::F3);
+    assert!(matches!(
+        HotkeyKey::read_le(&mut cursor).unwrap(),
+        HotkeyKey::Raw(0xad)
+    ));
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    HotkeyKey::F3.write_le(&mut cursor).unwrap();
+    HotkeyKey::Raw(0xad).write_le(&mut cursor).unwrap();
+    assert_eq!(cursor.into_inner(), [0x72, 0xad]);
+}
+
+#[test]
+fn test_show_command() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.show_command(), ShowCommand::ShowNormal);
+
+    shortcut.set_show_command(ShowCommand::ShowMaximized);
+    assert_eq!(
+        *shortcut.header().show_command(),
+        ShowCommand::ShowMaximized
+    );
+    assert_eq!(shortcut.show_command().to_string(), "Maximized");
+}
+
+#[test]
+fn test_window_style() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.window_style(), 1);
+
+    shortcut.set_window_style(7).unwrap();
+    assert_eq!(shortcut.show_command(), ShowCommand::ShowMinNoActive);
+    assert_eq!(shortcut.window_style(), 7);
+
+    assert!(matches!(
+        shortcut.set_window_style(2),
+        Err(Error::InvalidWindowStyle { style: 2 })
+    ));
+    assert_eq!(shortcut.show_command(), ShowCommand::ShowMinNoActive);
+}
+
+#[test]
+fn test_ansi_link_with_unicode_environment_target() {
+    let _ = pretty_env_logger::try_init();
+
+    // an ANSI link, whose EnvironmentVariableDataBlock contains a Unicode
+    // path which cannot be represented in the code page
+    let env_target = r"%USERPROFILE%\Документы\app.exe";
+    let mut bytes = crafted_header(LinkFlags::HAS_NAME | LinkFlags::HAS_EXP_STRING);
+    bytes.extend_from_slice(&4u16.to_le_bytes());
+    bytes.extend_from_slice(b"Docs");
+    bytes.extend_from_slice(&0x314u32.to_le_bytes());
+    bytes.extend_from_slice(&0xa0000001u32.to_le_bytes());
+    let mut ansi = br"%USERPROFILE%\?????????\app.exe".to_vec();
+    ansi.resize(260, 0);
+    bytes.extend_from_slice(&ansi);
+    let mut unicode: Vec<u8> = env_target
+        .encode_utf16()
+        .flat_map(|c| c.to_le_bytes())
+        .collect();
+    unicode.resize(520, 0);
+    bytes.extend_from_slice(&unicode);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    let shortcut = ShellLink::from_bytes(&bytes, WINDOWS_1252).unwrap();
+    assert_eq!(shortcut.encoding_name(), "windows-1252");
+    assert_eq!(shortcut.description(), Some("Docs"));
+
+    let block = shortcut.extra_data().environment_variables().unwrap();
+    assert_eq!(block.target_ansi(), r"%USERPROFILE%\?????????\app.exe");
+    assert_eq!(block.target(), env_target);
+    assert_eq!(shortcut.environment_target(), Some(env_target));
+    assert_eq!(shortcut.resolve_target(), Some(env_target.to_string()));
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn test_utc_times() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252).unwrap();
+    for (time, filetime) in [
+        (shortcut.creation_time(), shortcut.header().creation_time()),
+        (shortcut.access_time(), shortcut.header().access_time()),
+        (shortcut.write_time(), shortcut.header().write_time()),
+    ] {
+        assert_eq!(time.unwrap().naive_utc(), filetime.datetime());
+    }
+
+    // 2009-07-25 23:00:00 UTC
+    shortcut
+        .header_mut()
+        .set_write_time(FileTime::from(128_930_364_000_000_000));
+    assert_eq!(
+        shortcut.write_time().unwrap().to_rfc3339(),
+        "2009-07-25T23:00:00+00:00"
+    );
+
+    shortcut.header_mut().set_write_time(FileTime::default());
+    assert_eq!(shortcut.write_time(), None);
+}
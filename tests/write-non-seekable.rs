@@ -0,0 +1,45 @@
+use lnk::{encoding::WINDOWS_1252, ShellLink};
+
+use std::fs;
+use std::io::Write;
+
+const TEST_FILE_NAME: &str = "temp-write-non-seekable.lnk";
+
+/// a writer which only implements [`Write`], like a pipe
+struct PipeWriter(Vec<u8>);
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_to_non_seekable() {
+    let _ = pretty_env_logger::try_init();
+
+    let mut shortcut = ShellLink::default();
+    shortcut.set_name(Some("Piped name".to_string()));
+    shortcut.set_arguments(Some("--flag".to_string()));
+
+    let mut pipe = PipeWriter(Vec::new());
+    shortcut.write_to_non_seekable(&mut pipe).unwrap();
+
+    fs::write(TEST_FILE_NAME, &pipe.0).unwrap();
+    let shortcut = ShellLink::open(TEST_FILE_NAME, WINDOWS_1252);
+    fs::remove_file(TEST_FILE_NAME).unwrap();
+
+    let shortcut = shortcut.unwrap();
+    assert_eq!(
+        shortcut.string_data().name_string(),
+        &Some("Piped name".to_string())
+    );
+    assert_eq!(
+        shortcut.string_data().command_line_arguments(),
+        &Some("--flag".to_string())
+    );
+}